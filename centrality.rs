@@ -0,0 +1,282 @@
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+use crate::graph::{bfs_distances, Graph, Vertex, WeightedAdjacencyList};
+use crate::paths::State;
+
+// betweenness centrality using brandes' algorithm, source used: https://www.cl.cam.ac.uk/teaching/1617/MLRD/handbook/brandes.pdf
+// for every source we do a bfs that counts the shortest paths to each node, then go back through the nodes
+// from farthest to closest adding up how much each node depends on the ones after it
+// raw values count the (unordered, for undirected graphs) pairs whose shortest paths go through a node.
+// with normalized = true they're divided by the number of pairs not including the node so graphs of different sizes compare
+pub fn betweenness_centrality(graph: &Graph, normalized: bool) -> HashMap<Vertex, f64> {
+    let mut centrality: HashMap<Vertex, f64> = graph.nodes().map(|v| (v, 0.0)).collect();
+
+    for source in graph.nodes() {
+        let mut order = Vec::new(); //nodes in the order bfs finished them
+        let mut predecessors: HashMap<Vertex, Vec<Vertex>> = HashMap::new();
+        let mut path_counts: HashMap<Vertex, f64> = HashMap::from([(source, 1.0)]); //number of shortest paths from source
+        let mut distances: HashMap<Vertex, usize> = HashMap::from([(source, 0)]);
+        let mut queue = VecDeque::from([source]);
+
+        while let Some(current) = queue.pop_front() {
+            order.push(current);
+            let distance = distances[&current];
+            for neighbor in graph.neighbors(current) {
+                if let Entry::Vacant(entry) = distances.entry(neighbor) {
+                    entry.insert(distance + 1);
+                    queue.push_back(neighbor);
+                }
+                if distances[&neighbor] == distance + 1 { //current is right before neighbor on a shortest path
+                    *path_counts.entry(neighbor).or_insert(0.0) += path_counts[&current];
+                    predecessors.entry(neighbor).or_default().push(current);
+                }
+            }
+        }
+
+        add_dependencies(&mut centrality, source, order, &predecessors, &path_counts);
+    }
+
+    scale_betweenness(&mut centrality, graph.is_directed(), normalized);
+    centrality
+}
+
+// the second half of brandes for one source: go back through the nodes from farthest to closest (order is the
+// order they were finished in) adding up how much each node depends on the ones after it
+fn add_dependencies(
+    centrality: &mut HashMap<Vertex, f64>,
+    source: Vertex,
+    mut order: Vec<Vertex>,
+    predecessors: &HashMap<Vertex, Vec<Vertex>>,
+    path_counts: &HashMap<Vertex, f64>,
+) {
+    let mut dependency: HashMap<Vertex, f64> = HashMap::new();
+    while let Some(w) = order.pop() {
+        let w_dependency = dependency.get(&w).copied().unwrap_or(0.0);
+        for &v in predecessors.get(&w).into_iter().flatten() {
+            *dependency.entry(v).or_insert(0.0) += path_counts[&v] / path_counts[&w] * (1.0 + w_dependency);
+        }
+        if w != source {
+            *centrality.get_mut(&w).unwrap() += w_dependency;
+        }
+    }
+}
+
+fn scale_betweenness(centrality: &mut HashMap<Vertex, f64>, directed: bool, normalized: bool) {
+    let n = centrality.len() as f64;
+    let mut scale = if directed { 1.0 } else { 0.5 }; //undirected pairs got counted once from each end
+    if normalized {
+        let pairs = (n - 1.0) * (n - 2.0) * scale; //pairs of other nodes
+        scale = if pairs > 0.0 { scale / pairs } else { 0.0 };
+    }
+    for value in centrality.values_mut() {
+        *value *= scale;
+    }
+}
+
+// betweenness like betweenness_centrality but the shortest paths are by total weight instead of hops, so the bfs
+// for each source is a dijkstra that also counts the paths. weights should be positive, and two paths whose
+// weights add up to the same thing (within rounding) both count as shortest. the weighted list is undirected
+// (build_weighted_adjacency_list adds both ways) so pairs are unordered like the undirected unweighted version.
+// this is slower than betweenness_centrality because of the heap, that's why it's separate
+pub fn weighted_betweenness_centrality(graph: &WeightedAdjacencyList, normalized: bool) -> HashMap<Vertex, f64> {
+    let mut centrality: HashMap<Vertex, f64> = graph.keys().map(|&v| (v, 0.0)).collect();
+
+    for &source in graph.keys() {
+        let mut order = Vec::new(); //nodes in the order dijkstra settled them
+        let mut predecessors: HashMap<Vertex, Vec<Vertex>> = HashMap::new();
+        let mut path_counts: HashMap<Vertex, f64> = HashMap::from([(source, 1.0)]);
+        let mut distances: HashMap<Vertex, f64> = HashMap::from([(source, 0.0)]);
+        let mut heap = BinaryHeap::from([State { distance: 0.0, vertex: source }]);
+
+        while let Some(State { distance, vertex }) = heap.pop() {
+            if distance > distances[&vertex] {
+                continue; //stale entry, vertex was already settled closer
+            }
+            order.push(vertex);
+            let count = path_counts[&vertex];
+            for (&neighbor, &weight) in &graph[&vertex] {
+                let next = distance + weight;
+                match distances.get(&neighbor) {
+                    Some(&best) if (next - best).abs() <= 1e-9 * best.max(1.0) => { //another shortest path
+                        *path_counts.get_mut(&neighbor).unwrap() += count;
+                        predecessors.entry(neighbor).or_default().push(vertex);
+                    }
+                    Some(&best) if best < next => {}
+                    _ => {
+                        distances.insert(neighbor, next);
+                        path_counts.insert(neighbor, count);
+                        predecessors.insert(neighbor, vec![vertex]);
+                        heap.push(State { distance: next, vertex: neighbor });
+                    }
+                }
+            }
+        }
+
+        add_dependencies(&mut centrality, source, order, &predecessors, &path_counts);
+    }
+
+    scale_betweenness(&mut centrality, false, normalized);
+    centrality
+}
+
+// closeness centrality: 1 / average distance to the other nodes, from a bfs at every node
+// for disconnected graphs it only averages over the nodes that can be reached and then scales by the
+// fraction of the graph that was reached (the wasserman-faust version), so a small component doesn't look central
+// a node that can't reach anything gets 0.0
+pub fn closeness_centrality(graph: &Graph) -> HashMap<Vertex, f64> {
+    let n = graph.num_nodes();
+    graph
+        .nodes()
+        .map(|v| {
+            let distances = bfs_distances(graph, v);
+            let reached = distances.len() - 1; //not counting v itself
+            let total: usize = distances.values().sum();
+            if reached == 0 {
+                return (v, 0.0);
+            }
+            let closeness = reached as f64 / total as f64 * (reached as f64 / (n - 1) as f64);
+            (v, closeness)
+        })
+        .collect()
+}
+
+// harmonic centrality: the sum of 1 / distance to every other node, from a bfs at every node. a node that can't
+// be reached is 1 / infinity so it just adds 0, that's why this works on the twitch graph's little components
+// without the reached-fraction fix closeness needs. with normalized = true it's divided by n - 1 so it's 1.0 for
+// a node next to everything. an isolated node gets 0.0
+pub fn harmonic_centrality(graph: &Graph, normalized: bool) -> HashMap<Vertex, f64> {
+    let n = graph.num_nodes();
+    let scale = if normalized && n > 1 { 1.0 / (n - 1) as f64 } else { 1.0 };
+    graph
+        .nodes()
+        .map(|v| {
+            let total: f64 = bfs_distances(graph, v).values().filter(|&&d| d > 0).map(|&d| 1.0 / d as f64).sum();
+            (v, total * scale)
+        })
+        .collect()
+}
+
+// the usual damping factor for pagerank, the chance of following a link instead of jumping to a random node
+pub const DEFAULT_DAMPING: f64 = 0.85;
+
+// pagerank with the power method: start every node at 1/n and repeatedly hand each node's rank out to its
+// neighbors (out-neighbors for a directed graph). nodes with no out-edges (dangling) spread their rank
+// over every node evenly so nothing leaks out, which keeps the scores adding up to 1
+pub fn pagerank(graph: &Graph, damping: f64, iterations: usize) -> HashMap<Vertex, f64> {
+    let n = graph.num_nodes() as f64;
+    let mut ranks: HashMap<Vertex, f64> = graph.nodes().map(|v| (v, 1.0 / n)).collect();
+
+    for _ in 0..iterations {
+        let dangling: f64 = ranks.iter().filter(|&(&v, _)| graph.degree(v) == 0).map(|(_, &rank)| rank).sum();
+        let base = (1.0 - damping) / n + damping * dangling / n;
+        let mut next: HashMap<Vertex, f64> = graph.nodes().map(|v| (v, base)).collect();
+
+        for (&v, &rank) in &ranks {
+            let degree = graph.degree(v);
+            for neighbor in graph.neighbors(v) {
+                *next.get_mut(&neighbor).unwrap() += damping * rank / degree as f64;
+            }
+        }
+        ranks = next;
+    }
+
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{build_adjacency_list, build_weighted_adjacency_list, with_unit_weights, Direction};
+
+    #[test]
+    fn betweenness_on_a_path() {
+        // 1-2-3-4-5: 3 is on the paths of 1-4, 1-5, 2-4, 2-5 so 4 pairs, 2 is on 1-3, 1-4, 1-5 so 3 pairs
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5)]);
+        let raw = betweenness_centrality(&graph, false);
+        assert_eq!(raw[&1], 0.0);
+        assert_eq!(raw[&2], 3.0);
+        assert_eq!(raw[&3], 4.0);
+        assert_eq!(raw[&4], 3.0);
+
+        let normalized = betweenness_centrality(&graph, true);
+        assert!((normalized[&3] - 4.0 / 6.0).abs() < 1e-9); //6 pairs of the other 4 nodes
+        let middle = normalized.iter().max_by(|a, b| a.1.total_cmp(b.1)).unwrap();
+        assert_eq!(*middle.0, 3);
+    }
+
+    #[test]
+    fn betweenness_splits_between_equal_paths() {
+        // square 1-2-3-4-1: the two shortest paths from 1 to 3 go through 2 and 4, so each gets half
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 4), (4, 1)]);
+        let raw = betweenness_centrality(&graph, false);
+        assert!(raw.values().all(|&value| (value - 0.5).abs() < 1e-9));
+    }
+
+    #[test]
+    fn weighted_betweenness_follows_the_weights() {
+        // square 1-2-3-4-1 but the 1-4-3 side is heavy, so both 1-3 paths go through 2 now
+        let graph = build_weighted_adjacency_list(&[(1, 2, 1.0), (2, 3, 1.0), (3, 4, 2.0), (4, 1, 2.0)]);
+        let raw = weighted_betweenness_centrality(&graph, false);
+        assert_eq!(raw[&2], 1.0);
+        assert_eq!(raw[&4], 0.0);
+        assert_eq!(raw[&1], 0.5); //2-4 is 3 either way, through 1 or 3
+        assert_eq!(raw[&3], 0.5);
+
+        // with the same weight everywhere it's the same as the unweighted version
+        let edges = [(1, 2), (2, 3), (3, 4), (4, 5), (2, 6), (6, 4)];
+        let unweighted = betweenness_centrality(&Graph::from_edges(&edges), true);
+        let weighted = weighted_betweenness_centrality(&with_unit_weights(&build_adjacency_list(&edges)), true);
+        for (v, value) in unweighted {
+            assert!((weighted[&v] - value).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn closeness() {
+        // path 1-2-3 plus a separate 4-5 edge, n = 5
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (4, 5)]);
+        let closeness = closeness_centrality(&graph);
+        assert!((closeness[&2] - 1.0 * 0.5).abs() < 1e-9); //distances 1 + 1, reached 2 of 4 others
+        assert!((closeness[&1] - 2.0 / 3.0 * 0.5).abs() < 1e-9);
+        assert!((closeness[&4] - 0.25).abs() < 1e-9);
+
+        let directed = Graph::from_edges_with_direction(&[(1, 2)], Direction::Directed);
+        assert_eq!(closeness_centrality(&directed)[&2], 0.0); //2 has no out-edges
+    }
+
+    #[test]
+    fn harmonic() {
+        // same graph as closeness: path 1-2-3 plus a separate 4-5 edge
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (4, 5)]);
+        let raw = harmonic_centrality(&graph, false);
+        assert_eq!(raw[&2], 2.0);
+        assert_eq!(raw[&1], 1.5); //1/1 + 1/2
+        assert_eq!(raw[&4], 1.0);
+
+        let normalized = harmonic_centrality(&graph, true);
+        assert_eq!(normalized[&2], 0.5);
+        let lonely = Graph::from_adjacency_list(HashMap::from([(1, Default::default())]));
+        assert_eq!(harmonic_centrality(&lonely, true)[&1], 0.0);
+    }
+
+    #[test]
+    fn pagerank_small_graph() {
+        // 1 -> 2, 1 -> 3, 2 -> 3, 3 -> 1, solving the pagerank equations by hand with d = 0.85 gives these
+        let graph = Graph::from_edges_with_direction(&[(1, 2), (1, 3), (2, 3), (3, 1)], Direction::Directed);
+        let ranks = pagerank(&graph, DEFAULT_DAMPING, 100);
+        assert!((ranks[&1] - 0.387789).abs() < 1e-5);
+        assert!((ranks[&2] - 0.214811).abs() < 1e-5);
+        assert!((ranks[&3] - 0.397400).abs() < 1e-5);
+        assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pagerank_dangling_node() {
+        let graph = Graph::from_edges_with_direction(&[(1, 2), (3, 2)], Direction::Directed); //2 has no out-edges
+        let ranks = pagerank(&graph, DEFAULT_DAMPING, 50);
+        assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(ranks[&2] > ranks[&1]);
+        assert_eq!(ranks[&1], ranks[&3]);
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::csr::Relabeling;
+use crate::graph::{Graph, Vertex};
+
+// how many pairs of v's neighbors are connected to each other (the closed triplets centered at v).
+// a self-loop puts v in its own neighbors, it's left out so v-v-b doesn't count as a closed pair
+fn linked_neighbor_pairs(graph: &Graph, v: Vertex) -> usize {
+    let neighbors: Vec<Vertex> = graph.neighbors(v).filter(|&w| w != v).collect();
+    let mut links = 0;
+    for (i, &a) in neighbors.iter().enumerate() {
+        for &b in &neighbors[i + 1..] {
+            if graph.has_edge(a, b) {
+                links += 1;
+            }
+        }
+    }
+    links
+}
+
+// degree of v not counting a self-loop, since those aren't part of any triplet
+fn loop_free_degree(graph: &Graph, v: Vertex) -> usize {
+    graph.degree(v) - usize::from(graph.has_edge(v, v))
+}
+
+// number of distinct triangles, every triangle gets found once from each of its 3 corners so divide by 3
+pub fn count_triangles(graph: &Graph) -> usize {
+    graph.nodes().map(|v| linked_neighbor_pairs(graph, v)).sum::<usize>() / 3
+}
+
+// how many triangles each node is in, which is the number of edges among its neighbors. the nodes with the most
+// are the ones sitting in the densest neighborhoods. every node in the graph is in the map, with 0 if it's in none
+pub fn node_triangle_counts(graph: &Graph) -> HashMap<Vertex, usize> {
+    graph.nodes().map(|v| (v, linked_neighbor_pairs(graph, v))).collect()
+}
+
+// approximate number of triangles by wedge sampling, for when count_triangles is too slow. a wedge is a path a-v-b
+// centered at v, every triangle closes exactly 3 of them, so triangles = closed fraction * all wedges / 3. this
+// picks sample_fraction of the wedges at random (each one equally likely, so a node with degree k gets picked
+// k*(k-1)/2 times as often), checks which ones are closed and scales up. 1.0 or more just does the exact count.
+// the work is one sorted copy of the neighbor lists (and an undirected copy first if the graph is directed) plus
+// the samples, but the error only shrinks like 1 / sqrt(samples) and gets worse when few wedges are closed, so a
+// tiny fraction of a huge graph is fine and a small graph should just use count_triangles. edge directions and
+// self-loops are ignored either way, and the same seed gives the same estimate
+pub fn estimate_triangles(graph: &Graph, sample_fraction: f64, seed: u64) -> f64 {
+    let graph = &*graph.as_undirected();
+    if sample_fraction >= 1.0 {
+        return count_triangles(graph) as f64;
+    }
+    let labels = Relabeling::new(graph.nodes().collect());
+    let neighbors: Vec<Vec<Vertex>> = labels
+        .inverse()
+        .iter()
+        .map(|&v| {
+            let mut list: Vec<Vertex> = graph.neighbors(v).filter(|&w| w != v).collect(); //no self-loops, like count_triangles
+            list.sort_unstable(); //hashset order changes between runs, the seed should be all that decides
+            list
+        })
+        .collect();
+
+    let mut running = 0u64;
+    let wedges_up_to: Vec<u64> = neighbors // running total so a random number picks a center by its wedge count
+        .iter()
+        .map(|list| {
+            let k = list.len() as u64;
+            running += k * k.saturating_sub(1) / 2;
+            running
+        })
+        .collect();
+    let total = running;
+    if total == 0 {
+        return 0.0;
+    }
+
+    let samples = ((sample_fraction.max(0.0) * total as f64).ceil() as u64).max(1);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut closed = 0u64;
+    for _ in 0..samples {
+        let pick = rng.gen_range(0..total);
+        let center = &neighbors[wedges_up_to.partition_point(|&upto| upto <= pick)];
+        let i = rng.gen_range(0..center.len());
+        let mut j = rng.gen_range(0..center.len() - 1); //a different neighbor than i
+        if j >= i {
+            j += 1;
+        }
+        if graph.has_edge(center[i], center[j]) {
+            closed += 1;
+        }
+    }
+    closed as f64 / samples as f64 * total as f64 / 3.0
+}
+
+// global clustering coefficient: closed triplets / all connected triplets (same as 3 * triangles / triplets)
+// every node with degree k is the center of k*(k-1)/2 triplets. edge directions are ignored (a directed graph
+// gets converted first). returns 0.0 when there are no triplets at all instead of NaN
+pub fn global_clustering_coefficient(graph: &Graph) -> f64 {
+    let graph = &*graph.as_undirected();
+    let mut closed_triplets = 0;
+    let mut triplets = 0;
+
+    for v in graph.nodes() {
+        let k = loop_free_degree(graph, v);
+        triplets += k * k.saturating_sub(1) / 2;
+        closed_triplets += linked_neighbor_pairs(graph, v);
+    }
+
+    if triplets == 0 {
+        return 0.0;
+    }
+    closed_triplets as f64 / triplets as f64
+}
+
+// local clustering coefficient of v: edges among its neighbors / the most there could be (k*(k-1)/2)
+// nodes with degree 0 or 1 get 0.0
+pub fn local_clustering_coefficient(graph: &Graph, v: Vertex) -> f64 {
+    let k = loop_free_degree(graph, v);
+    if k < 2 {
+        return 0.0;
+    }
+    linked_neighbor_pairs(graph, v) as f64 / (k * (k - 1) / 2) as f64
+}
+
+// local clustering coefficient for every node, handy for ranking them
+pub fn all_local_clustering(graph: &Graph) -> HashMap<Vertex, f64> {
+    graph.nodes().map(|v| (v, local_clustering_coefficient(graph, v))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Direction;
+
+    #[test]
+    fn global_clustering() {
+        let triangle = Graph::from_edges(&[(1, 2), (2, 3), (3, 1)]);
+        assert_eq!(global_clustering_coefficient(&triangle), 1.0);
+
+        // triangle with a tail: 1 triangle, triplets are 1 + 1 + 3 + 0 = 5 so 3/5
+        let tailed = Graph::from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4)]);
+        assert!((global_clustering_coefficient(&tailed) - 0.6).abs() < 1e-9);
+
+        let single_edge = Graph::from_edges(&[(1, 2)]);
+        assert_eq!(global_clustering_coefficient(&single_edge), 0.0);
+
+        let cycle = Graph::from_edges_with_direction(&[(1, 2), (2, 3), (3, 1)], Direction::Directed);
+        assert_eq!(global_clustering_coefficient(&cycle), 1.0); //same as the undirected triangle
+    }
+
+    #[test]
+    fn local_clustering() {
+        // 1-2-3 triangle plus 3-4, so 3 has 1 link out of 3 possible between its neighbors
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4)]);
+        assert_eq!(local_clustering_coefficient(&graph, 1), 1.0);
+        assert!((local_clustering_coefficient(&graph, 3) - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(local_clustering_coefficient(&graph, 4), 0.0); //degree 1
+        assert_eq!(local_clustering_coefficient(&graph, 99), 0.0); //not in the graph
+
+        let all = all_local_clustering(&graph);
+        assert_eq!(all.len(), 4);
+        assert_eq!(all[&2], 1.0);
+    }
+
+    #[test]
+    fn triangles() {
+        let k4 = Graph::from_edges(&[(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)]);
+        assert_eq!(count_triangles(&k4), 4);
+        let square = Graph::from_edges(&[(1, 2), (2, 3), (3, 4), (4, 1)]);
+        assert_eq!(count_triangles(&square), 0);
+    }
+
+    #[test]
+    fn self_loops_are_not_triangles() {
+        // path 1-2-3 where 2 also links to itself, so 2-2-3 and 1-2-2 would look closed if the loop counted
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (2, 2)]);
+        assert_eq!(count_triangles(&graph), 0);
+        assert_eq!(node_triangle_counts(&graph)[&2], 0);
+        assert_eq!(local_clustering_coefficient(&graph, 2), 0.0);
+        assert_eq!(global_clustering_coefficient(&graph), 0.0);
+
+        let triangle = Graph::from_edges(&[(1, 2), (2, 3), (3, 1), (1, 1)]);
+        assert_eq!(count_triangles(&triangle), 1);
+        assert_eq!(local_clustering_coefficient(&triangle, 1), 1.0); //the loop doesn't add a triplet either
+        assert_eq!(global_clustering_coefficient(&triangle), 1.0);
+    }
+
+    #[test]
+    fn triangles_per_node() {
+        // two triangles 1-2-3 and 2-3-4 sharing the 2-3 edge, plus 4-5 hanging off
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 1), (2, 4), (3, 4), (4, 5)]);
+        let counts = node_triangle_counts(&graph);
+        assert_eq!(counts, HashMap::from([(1, 1), (2, 2), (3, 2), (4, 1), (5, 0)]));
+        assert_eq!(counts.values().sum::<usize>() / 3, count_triangles(&graph));
+    }
+
+    #[test]
+    fn estimated_triangles_are_close() {
+        let graph = Graph::from_edges(&crate::generators::erdos_renyi(300, 0.1, 3));
+        let exact = count_triangles(&graph) as f64;
+        let estimate = estimate_triangles(&graph, 0.2, 9);
+        assert!((estimate - exact).abs() < 0.15 * exact, "estimate {} vs exact {}", estimate, exact);
+        assert_eq!(estimate, estimate_triangles(&graph, 0.2, 9));
+
+        let k4 = Graph::from_edges(&[(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)]);
+        assert_eq!(estimate_triangles(&k4, 0.5, 1), 4.0); //every wedge is closed so any sample gets it right
+        assert_eq!(estimate_triangles(&k4, 1.0, 1), 4.0);
+        assert_eq!(estimate_triangles(&Graph::from_edges(&[(1, 2)]), 0.5, 1), 0.0);
+
+        let cycle = Graph::from_edges_with_direction(&[(1, 2), (2, 3), (3, 1)], Direction::Directed);
+        assert_eq!(estimate_triangles(&cycle, 1.0, 1), 1.0); //directions are ignored on the exact path too
+        assert_eq!(estimate_triangles(&cycle, 0.5, 1), 1.0);
+
+        let looped = Graph::from_edges(&[(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4), (1, 1)]); //k4 plus a self-loop
+        assert_eq!(estimate_triangles(&looped, 1.0, 1), 4.0);
+        assert_eq!(estimate_triangles(&looped, 0.5, 1), 4.0);
+    }
+}
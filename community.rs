@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::csr::Relabeling;
+use crate::graph::{Graph, Vertex};
+
+// one level of louvain: a weighted graph over 0..n where each node is a community of the level below
+struct LouvainLevel {
+    neighbors: Vec<Vec<(usize, f64)>>, // edges to the other nodes, in both directions' lists
+    self_loops: Vec<f64>,              // weight of the edges inside each node (from communities that got merged)
+}
+
+impl LouvainLevel {
+    fn from_graph(graph: &Graph, labels: &Relabeling) -> LouvainLevel {
+        let mut level = LouvainLevel { neighbors: vec![Vec::new(); labels.len()], self_loops: vec![0.0; labels.len()] };
+        for (i, &v) in labels.inverse().iter().enumerate() {
+            for neighbor in graph.neighbors(v) {
+                match labels.to_new(neighbor).unwrap() {
+                    j if j == i => level.self_loops[i] += 1.0,
+                    j => level.neighbors[i].push((j, 1.0)),
+                }
+            }
+        }
+        level
+    }
+
+    fn len(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    // weighted degree, a self-loop counts from both of its ends
+    fn degree(&self, i: usize) -> f64 {
+        self.neighbors[i].iter().map(|&(_, w)| w).sum::<f64>() + 2.0 * self.self_loops[i]
+    }
+
+    // phase 1: keep moving single nodes to the neighboring community that raises modularity the most until no
+    // move helps. returns each node's community (renumbered to 0..c) and whether anything moved at all
+    fn local_moves(&self) -> (Vec<usize>, bool) {
+        let n = self.len();
+        let degrees: Vec<f64> = (0..n).map(|i| self.degree(i)).collect();
+        let total: f64 = degrees.iter().sum(); //2m
+        let mut community: Vec<usize> = (0..n).collect();
+        let mut community_degree = degrees.clone(); //sum of the degrees in each community
+        let mut moved_any = false;
+        if total == 0.0 {
+            return (community, false);
+        }
+
+        loop {
+            let mut moved = false;
+            for i in 0..n {
+                let current = community[i];
+                let mut links: HashMap<usize, f64> = HashMap::new(); //community -> weight of i's edges into it
+                for &(j, w) in &self.neighbors[i] {
+                    *links.entry(community[j]).or_insert(0.0) += w;
+                }
+                community_degree[current] -= degrees[i];
+
+                // gain from putting i into c, leaving out the parts that are the same for every c
+                let gain = |c: usize| links.get(&c).copied().unwrap_or(0.0) - community_degree[c] * degrees[i] / total;
+                let mut best = current;
+                let mut best_gain = gain(current);
+                let mut candidates: Vec<usize> = links.keys().copied().collect();
+                candidates.sort_unstable(); //so ties always go the same way
+                for c in candidates {
+                    let g = gain(c);
+                    if g > best_gain + 1e-12 {
+                        best = c;
+                        best_gain = g;
+                    }
+                }
+
+                community_degree[best] += degrees[i];
+                if best != current {
+                    community[i] = best;
+                    moved = true;
+                    moved_any = true;
+                }
+            }
+            if !moved {
+                break;
+            }
+        }
+
+        (renumber(&community), moved_any)
+    }
+
+    // phase 2: every community becomes one node, edges between communities add up and the ones inside become a self-loop
+    fn aggregate(&self, community: &[usize]) -> LouvainLevel {
+        let count = community.iter().max().map_or(0, |&c| c + 1);
+        let mut weights: Vec<HashMap<usize, f64>> = vec![HashMap::new(); count];
+        let mut self_loops = vec![0.0; count];
+        for i in 0..self.len() {
+            let ci = community[i];
+            self_loops[ci] += self.self_loops[i];
+            for &(j, w) in &self.neighbors[i] {
+                let cj = community[j];
+                if ci == cj {
+                    self_loops[ci] += w / 2.0; //each inside edge shows up from both ends
+                } else {
+                    *weights[ci].entry(cj).or_insert(0.0) += w;
+                }
+            }
+        }
+        let neighbors = weights
+            .into_iter()
+            .map(|links| {
+                let mut links: Vec<(usize, f64)> = links.into_iter().collect();
+                links.sort_unstable_by_key(|&(j, _)| j);
+                links
+            })
+            .collect();
+        LouvainLevel { neighbors, self_loops }
+    }
+}
+
+// community ids in order of first appearance, so they're 0..c with no gaps
+fn renumber(community: &[usize]) -> Vec<usize> {
+    let mut ids: HashMap<usize, usize> = HashMap::new();
+    community
+        .iter()
+        .map(|&c| {
+            let next = ids.len();
+            *ids.entry(c).or_insert(next)
+        })
+        .collect()
+}
+
+// communities with the louvain method, source used: https://arxiv.org/abs/0803.0476 (blondel et al.)
+// phase 1 moves nodes one at a time to whichever neighboring community raises the modularity most, phase 2
+// merges each community into a single node, and that repeats on the smaller graph until nothing moves.
+// node -> community id, the ids are 0..c numbered by the smallest node in each community.
+// edge directions are ignored. nodes are visited in sorted order rather than randomly so the result is always the same
+pub fn louvain_communities(graph: &Graph) -> HashMap<Vertex, usize> {
+    let graph = graph.as_undirected();
+    let labels = Relabeling::new(graph.nodes().collect());
+    let mut level = LouvainLevel::from_graph(&graph, &labels);
+    let mut membership: Vec<usize> = (0..labels.len()).collect(); //original node index -> node in the current level
+
+    loop {
+        let (community, moved) = level.local_moves();
+        if !moved {
+            break;
+        }
+        for m in membership.iter_mut() {
+            *m = community[*m];
+        }
+        level = level.aggregate(&community);
+    }
+
+    let membership = renumber(&membership); //labels are sorted so this numbers them by smallest node
+    labels.inverse().iter().copied().zip(membership).collect()
+}
+
+// communities by label propagation, source used: https://arxiv.org/abs/0709.2938 (raghavan et al.)
+// every node starts with its own label, then in each round the nodes go in a random order and each one takes
+// the label most of its neighbors have. a node keeps its label if that's one of the most common ones, other
+// ties are picked at random with `seed` so a run can be repeated. it stops when a round changes nothing or after
+// max_iters rounds. each round is one pass over the edges. the communities it ends up with depend on the seed.
+// node -> community id numbered 0..c by the smallest node like louvain_communities, directions are ignored
+pub fn label_propagation(graph: &Graph, max_iters: usize, seed: u64) -> HashMap<Vertex, usize> {
+    let graph = graph.as_undirected();
+    let labels = Relabeling::new(graph.nodes().collect());
+    let neighbors: Vec<Vec<usize>> = labels
+        .inverse()
+        .iter()
+        .map(|&v| graph.neighbors(v).map(|w| labels.to_new(w).unwrap()).collect())
+        .collect();
+    let mut label: Vec<usize> = (0..labels.len()).collect();
+    let mut order: Vec<usize> = (0..labels.len()).collect();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut counts: HashMap<usize, usize> = HashMap::new();
+    let mut best: Vec<usize> = Vec::new();
+
+    for _ in 0..max_iters {
+        order.shuffle(&mut rng);
+        let mut changed = false;
+        for &i in &order {
+            if neighbors[i].is_empty() {
+                continue;
+            }
+            counts.clear();
+            for &j in &neighbors[i] {
+                *counts.entry(label[j]).or_insert(0) += 1;
+            }
+            let most = *counts.values().max().unwrap();
+            if counts.get(&label[i]) == Some(&most) {
+                continue;
+            }
+            best.clear();
+            best.extend(counts.iter().filter(|&(_, &count)| count == most).map(|(&l, _)| l));
+            best.sort_unstable(); //hashmap order isn't the same between runs, the seed should be all that decides
+            label[i] = *best.choose(&mut rng).unwrap();
+            changed = true;
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    labels.inverse().iter().copied().zip(renumber(&label)).collect()
+}
+
+// modularity of a partition (node -> community id) of an undirected graph: the fraction of edges inside the
+// communities minus what that fraction would be if the edges were wired up at random with the same degrees,
+// Q = sum over communities of in_c / 2m - (degree_c / 2m)^2. higher is better, around 0.3 and up is usually
+// real community structure, and 0.0 for a graph with no edges. a node that isn't in the map counts as its own community
+pub fn modularity(graph: &Graph, communities: &HashMap<Vertex, usize>) -> f64 {
+    let community_of = |v: Vertex| communities.get(&v).copied().ok_or(v); //Err(v) keeps missing nodes apart
+    let mut inside: HashMap<Result<usize, Vertex>, f64> = HashMap::new(); //edge ends inside each community, so 2x the edges
+    let mut degree: HashMap<Result<usize, Vertex>, f64> = HashMap::new();
+    let mut total = 0.0; //2m
+
+    for v in graph.nodes() {
+        let c = community_of(v);
+        for neighbor in graph.neighbors(v) {
+            total += 1.0;
+            *degree.entry(c).or_insert(0.0) += 1.0;
+            if community_of(neighbor) == c {
+                *inside.entry(c).or_insert(0.0) += 1.0;
+            }
+        }
+    }
+    if total == 0.0 {
+        return 0.0;
+    }
+
+    degree.iter().map(|(c, &d)| inside.get(c).copied().unwrap_or(0.0) / total - (d / total).powi(2)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Edge;
+
+    // every pair of nodes in the list
+    fn clique(nodes: &[Vertex]) -> Vec<Edge> {
+        let mut edges = Vec::new();
+        for (i, &u) in nodes.iter().enumerate() {
+            for &v in &nodes[i + 1..] {
+                edges.push((u, v));
+            }
+        }
+        edges
+    }
+
+    #[test]
+    fn two_cliques_split_apart() {
+        let mut edges = clique(&[1, 2, 3, 4, 5]);
+        edges.extend(clique(&[6, 7, 8, 9, 10]));
+        edges.push((5, 6)); //one edge between them
+        let communities = louvain_communities(&Graph::from_edges(&edges));
+
+        for v in 1..=5 {
+            assert_eq!(communities[&v], 0);
+        }
+        for v in 6..=10 {
+            assert_eq!(communities[&v], 1);
+        }
+        assert!(louvain_communities(&Graph::default()).is_empty());
+    }
+
+    #[test]
+    fn label_propagation_finds_the_cliques() {
+        let mut edges = clique(&[1, 2, 3, 4, 5]);
+        edges.extend(clique(&[6, 7, 8, 9, 10]));
+        edges.push((5, 6));
+        edges.push((20, 21)); //a separate pair
+        let graph = Graph::from_edges(&edges);
+        let communities = label_propagation(&graph, 100, 7);
+
+        assert!((1..=5).all(|v| communities[&v] == communities[&1]));
+        assert!((6..=10).all(|v| communities[&v] == communities[&6]));
+        assert_ne!(communities[&1], communities[&6]);
+        assert_eq!(communities[&20], communities[&21]);
+        assert_eq!(label_propagation(&graph, 100, 7), communities);
+        assert_eq!(label_propagation(&graph, 0, 7).len(), 12); //no rounds means everyone keeps their own label
+    }
+
+    #[test]
+    fn modularity_of_two_cliques() {
+        let mut edges = clique(&[1, 2, 3, 4, 5]);
+        edges.extend(clique(&[6, 7, 8, 9, 10]));
+        edges.push((5, 6));
+        let graph = Graph::from_edges(&edges);
+
+        let split: HashMap<Vertex, usize> = (1..=10).map(|v| (v, if v <= 5 { 0 } else { 1 })).collect();
+        let q = modularity(&graph, &split);
+        assert!((q - (20.0 / 21.0 - 0.5)).abs() < 1e-9); //20 of the 21 edges are inside, each half has half the degree
+        assert!(q > 0.4);
+
+        let together: HashMap<Vertex, usize> = (1..=10).map(|v| (v, 0)).collect();
+        assert!(modularity(&graph, &together).abs() < 1e-9);
+        assert!(modularity(&graph, &HashMap::new()) < 0.0); //everything on its own
+        assert!((modularity(&graph, &louvain_communities(&graph)) - q).abs() < 1e-9);
+        assert_eq!(modularity(&Graph::default(), &HashMap::new()), 0.0);
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+use crate::csr::Relabeling;
+use crate::graph::{connected_nodes, Edge, Graph, Vertex};
+use crate::union_find::UnionFind;
+
+// the biggest connected component (the giant component for the twitch data), empty for an empty graph
+pub fn largest_component(graph: &Graph) -> HashSet<Vertex> {
+    connected_nodes(graph).into_iter().max_by_key(|component| component.len()).unwrap_or_default()
+}
+
+// sizes of all the connected components, biggest first
+pub fn component_size_distribution(graph: &Graph) -> Vec<usize> {
+    let mut sizes: Vec<usize> = connected_nodes(graph).iter().map(|component| component.len()).collect();
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+    sizes
+}
+
+// node -> id of its connected component, handy for joining onto other data about the nodes.
+// ids go 0, 1, 2, ... with the components ordered by their smallest node, so the same graph always gets the same labels
+pub fn component_labels(graph: &Graph) -> HashMap<Vertex, usize> {
+    let mut components: Vec<(Vertex, HashSet<Vertex>)> =
+        connected_nodes(graph).into_iter().map(|component| (*component.iter().min().unwrap(), component)).collect();
+    components.sort_unstable_by_key(|&(smallest, _)| smallest);
+
+    let mut labels = HashMap::with_capacity(graph.num_nodes());
+    for (id, (_, component)) in components.into_iter().enumerate() {
+        labels.extend(component.into_iter().map(|v| (v, id)));
+    }
+    labels
+}
+
+// short summary of the connected components instead of every node id in them
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ComponentReport {
+    pub count: usize,
+    pub largest: usize,                            // size of the biggest component, 0 for an empty graph
+    pub smallest: usize,                           // size of the smallest one
+    pub size_distribution: BTreeMap<usize, usize>, // component size -> how many components are that size
+}
+
+pub fn component_report(graph: &Graph) -> ComponentReport {
+    let sizes = component_size_distribution(graph);
+    let mut size_distribution = BTreeMap::new();
+    for &size in &sizes {
+        *size_distribution.entry(size).or_insert(0) += 1;
+    }
+    ComponentReport {
+        count: sizes.len(),
+        largest: sizes.first().copied().unwrap_or(0),
+        smallest: sizes.last().copied().unwrap_or(0),
+        size_distribution,
+    }
+}
+
+impl fmt::Display for ComponentReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "connected components: {}", self.count)?;
+        writeln!(f, "largest: {} nodes, smallest: {} nodes", self.largest, self.smallest)?;
+        write!(f, "sizes:")?;
+        for (size, count) in self.size_distribution.iter().rev() {
+            write!(f, " {}x{}", count, size)?; //like 3x2 for three components of 2 nodes
+        }
+        Ok(())
+    }
+}
+
+// how many connected components the edges make, straight from the edge list with a union-find pass so
+// no adjacency list gets built. a node that only shows up in a self-loop like (v, v) counts as its own component
+pub fn count_components(edges: &[Edge]) -> usize {
+    let labels = Relabeling::from_edges(edges);
+    let mut sets = UnionFind::new(labels.len());
+    for &(u, v) in edges {
+        sets.union(labels.to_new(u).unwrap(), labels.to_new(v).unwrap());
+    }
+    sets.num_sets()
+}
+
+// weakly connected components: the pieces you get if you ignore which way the edges point, so u -> v puts
+// u and v together even when v can't get back to u. that's different from strongly_connected_components,
+// where both nodes have to reach each other following the directions, so every strong component sits
+// inside one weak component but a weak component can hold lots of strong ones.
+// works straight on a directed graph without making an undirected copy, it just keeps a list of who points
+// to each node (the in-neighbors) and the search walks both those and the normal out-neighbors.
+// on an undirected graph these are just the connected components
+pub fn weakly_connected_components(graph: &Graph) -> Vec<HashSet<Vertex>> {
+    let mut in_neighbors: HashMap<Vertex, Vec<Vertex>> = HashMap::new();
+    if graph.is_directed() {
+        for v in graph.nodes() {
+            for w in graph.neighbors(v) {
+                in_neighbors.entry(w).or_default().push(v);
+            }
+        }
+    }
+
+    let mut visited = HashSet::new();
+    let mut components = Vec::new();
+    for start in graph.nodes() {
+        if !visited.insert(start) {
+            continue;
+        }
+        let mut component = HashSet::from([start]);
+        let mut stack = vec![start];
+        while let Some(v) = stack.pop() {
+            let backwards = in_neighbors.get(&v).into_iter().flatten().copied();
+            for w in graph.neighbors(v).chain(backwards) {
+                if visited.insert(w) {
+                    component.insert(w);
+                    stack.push(w);
+                }
+            }
+        }
+        components.push(component);
+    }
+
+    components
+}
+
+struct TarjanFrame {
+    node: Vertex,
+    neighbors: Vec<Vertex>,
+    next: usize,
+}
+
+// strongly connected components: maximal sets where every node can reach every other one following edge directions.
+// tarjan's algorithm, source used: https://en.wikipedia.org/wiki/Tarjan%27s_strongly_connected_components_algorithm
+// done with an explicit stack like structure::low_link_dfs so long paths don't overflow. nodes stay on the tarjan
+// stack until their component is finished, and a node whose low-link is its own index is the root of one.
+// on an undirected graph these come out the same as connected_nodes
+pub fn strongly_connected_components(graph: &Graph) -> Vec<HashSet<Vertex>> {
+    let mut index: HashMap<Vertex, usize> = HashMap::new();
+    let mut low: HashMap<Vertex, usize> = HashMap::new();
+    let mut on_stack: HashSet<Vertex> = HashSet::new();
+    let mut stack: Vec<Vertex> = Vec::new();
+    let mut components = Vec::new();
+
+    for root in graph.nodes() {
+        if index.contains_key(&root) {
+            continue;
+        }
+        index.insert(root, index.len());
+        low.insert(root, index[&root]);
+        stack.push(root);
+        on_stack.insert(root);
+        let mut frames = vec![TarjanFrame { node: root, neighbors: graph.neighbors(root).collect(), next: 0 }];
+
+        while let Some(frame) = frames.last_mut() {
+            let v = frame.node;
+            if frame.next < frame.neighbors.len() {
+                let w = frame.neighbors[frame.next];
+                frame.next += 1;
+                if !index.contains_key(&w) {
+                    index.insert(w, index.len());
+                    low.insert(w, index[&w]);
+                    stack.push(w);
+                    on_stack.insert(w);
+                    frames.push(TarjanFrame { node: w, neighbors: graph.neighbors(w).collect(), next: 0 });
+                } else if on_stack.contains(&w) {
+                    let v_low = low.get_mut(&v).unwrap();
+                    *v_low = (*v_low).min(index[&w]);
+                }
+            } else {
+                frames.pop();
+                if low[&v] == index[&v] {
+                    let mut component = HashSet::new();
+                    while let Some(w) = stack.pop() {
+                        on_stack.remove(&w);
+                        component.insert(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+                if let Some(parent) = frames.last() {
+                    let v_low = low[&v];
+                    let p_low = low.get_mut(&parent.node).unwrap();
+                    *p_low = (*p_low).min(v_low);
+                }
+            }
+        }
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Direction;
+
+    #[test]
+    fn largest_and_sizes() {
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 4), (5, 6), (7, 8), (8, 9)]);
+        assert_eq!(largest_component(&graph), HashSet::from([1, 2, 3, 4]));
+        assert_eq!(component_size_distribution(&graph), vec![4, 3, 2]);
+        assert!(largest_component(&Graph::default()).is_empty());
+    }
+
+    #[test]
+    fn labels_are_contiguous_and_stable() {
+        let graph = Graph::from_edges(&[(10, 11), (3, 4), (4, 5), (1, 20)]);
+        let labels = component_labels(&graph);
+        assert_eq!(labels, HashMap::from([(1, 0), (20, 0), (3, 1), (4, 1), (5, 1), (10, 2), (11, 2)]));
+    }
+
+    #[test]
+    fn report_summary() {
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 4), (5, 6), (7, 8), (9, 10)]);
+        let report = component_report(&graph);
+        assert_eq!(report.count, 4);
+        assert_eq!((report.largest, report.smallest), (4, 2));
+        assert_eq!(report.size_distribution, BTreeMap::from([(2, 3), (4, 1)]));
+        assert_eq!(report.to_string(), "connected components: 4\nlargest: 4 nodes, smallest: 2 nodes\nsizes: 1x4 3x2");
+        assert_eq!(component_report(&Graph::default()), ComponentReport::default());
+    }
+
+    #[test]
+    fn counting_from_edges() {
+        let edges = [(1, 2), (2, 3), (3, 4), (5, 6), (7, 8), (8, 9), (10, 10)];
+        assert_eq!(count_components(&edges), 4);
+        assert_eq!(count_components(&edges), connected_nodes(&Graph::from_edges(&edges)).len());
+        assert_eq!(count_components(&[]), 0);
+    }
+
+    #[test]
+    fn strongly_connected() {
+        //1 -> 2 -> 3 -> 1 and 4 <-> 5, with only a one way edge 3 -> 4 between them
+        let edges = [(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 4), (5, 6)];
+        let graph = Graph::from_edges_with_direction(&edges, Direction::Directed);
+        let mut components = strongly_connected_components(&graph);
+        components.sort_by_key(|component| *component.iter().min().unwrap());
+        assert_eq!(components, vec![HashSet::from([1, 2, 3]), HashSet::from([4, 5]), HashSet::from([6])]);
+
+        let undirected = Graph::from_edges(&edges);
+        assert_eq!(strongly_connected_components(&undirected), vec![HashSet::from([1, 2, 3, 4, 5, 6])]);
+    }
+
+    #[test]
+    fn weakly_connected() {
+        let graph = Graph::from_edges_with_direction(&[(1, 2), (3, 2), (4, 5)], Direction::Directed);
+        let mut components = weakly_connected_components(&graph);
+        components.sort_by_key(|component| *component.iter().min().unwrap());
+        assert_eq!(components, vec![HashSet::from([1, 2, 3]), HashSet::from([4, 5])]);
+        assert!(graph.is_directed());
+        assert_eq!(strongly_connected_components(&graph).len(), 5);
+    }
+}
@@ -0,0 +1,324 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::graph::{hash_table_bytes, Direction, Edge, Graph, Vertex};
+
+// mapping between the original vertex ids (sparse, like the twitch ids) and 0..n, in order of the original id
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Relabeling {
+    forward: HashMap<Vertex, usize>, // original id -> new id
+    inverse: Vec<Vertex>,           // new id -> original id
+}
+
+impl Relabeling {
+    // vertices can have repeats and doesn't need to be sorted
+    pub fn new(mut vertices: Vec<Vertex>) -> Relabeling {
+        vertices.sort_unstable();
+        vertices.dedup();
+        let forward = vertices.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+        Relabeling { forward, inverse: vertices }
+    }
+
+    // relabeling of every vertex that shows up in the edges
+    pub fn from_edges(edges: &[Edge]) -> Relabeling {
+        Relabeling::new(edges.iter().flat_map(|&(u, v)| [u, v]).collect())
+    }
+
+    pub fn len(&self) -> usize {
+        self.inverse.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inverse.is_empty()
+    }
+
+    // new id of an original vertex, None if it wasn't in the relabeling
+    pub fn to_new(&self, v: Vertex) -> Option<usize> {
+        self.forward.get(&v).copied()
+    }
+
+    // original id of a new id
+    pub fn to_original(&self, index: usize) -> Vertex {
+        self.inverse[index]
+    }
+
+    // the same edges with both ends renamed to their new ids
+    pub fn apply(&self, edges: &[Edge]) -> Vec<Edge> {
+        edges.iter().map(|&(u, v)| (self.forward[&u], self.forward[&v])).collect()
+    }
+
+    // new id -> original id for every node
+    pub fn inverse(&self) -> &[Vertex] {
+        &self.inverse
+    }
+}
+
+// renames the vertices to 0..n so they can index a Vec. returns the renamed edges and the original id
+// of every new id (result.1[new] = original), see Relabeling for the id -> new id direction too
+pub fn relabel(edges: &[Edge]) -> (Vec<Edge>, Vec<Vertex>) {
+    let relabeling = Relabeling::from_edges(edges);
+    (relabeling.apply(edges), relabeling.inverse)
+}
+
+// compressed sparse row version of the graph: all the neighbor lists are in one flat Vec and
+// offsets[i]..offsets[i + 1] is the slice of it that belongs to node i. this uses much less memory
+// than a HashMap of HashSets and going through neighbors is just reading memory in order.
+// nodes are numbered 0..n inside with a Relabeling (sorted by their original id)
+// bfs on this is several times faster than graph::bfs_distances on the twitch graph, examples/bfs_bench.rs times
+// the two against each other (cargo run --release --example bfs_bench)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CsrGraph {
+    offsets: Vec<usize>,
+    neighbors: Vec<usize>,
+    labels: Relabeling,
+    direction: Direction,
+}
+
+impl CsrGraph {
+    pub fn from_edges(edges: &[Edge], direction: Direction) -> CsrGraph {
+        CsrGraph::build(Relabeling::from_edges(edges), edges, direction == Direction::Undirected, direction)
+    }
+
+    // every edge has to have both ends in the relabeling
+    fn build(labels: Relabeling, edges: &[Edge], both_ways: bool, direction: Direction) -> CsrGraph {
+        // every edge as (from, to) indices, twice when it goes both ways, sorted so each node's neighbors end up together
+        let mut arcs: Vec<(usize, usize)> = Vec::with_capacity(edges.len() * if both_ways { 2 } else { 1 });
+        for (i, j) in labels.apply(edges) {
+            arcs.push((i, j));
+            if both_ways {
+                arcs.push((j, i));
+            }
+        }
+        arcs.sort_unstable();
+        arcs.dedup(); //repeated edges only count once, same as the HashSet in the adjacency list
+
+        let mut offsets = vec![0; labels.len() + 1];
+        for &(from, _) in &arcs {
+            offsets[from + 1] += 1;
+        }
+        for i in 0..labels.len() {
+            offsets[i + 1] += offsets[i];
+        }
+        let mut neighbors: Vec<usize> = arcs.into_iter().map(|(_, to)| to).collect();
+        neighbors.shrink_to_fit(); //collect reuses the arcs allocation, which is twice as big as this needs
+
+        CsrGraph { offsets, neighbors, labels, direction }
+    }
+
+    // rough bytes used, the flat Vecs plus the relabeling (its HashMap counted the same way as graph::estimated_memory_bytes)
+    pub fn estimated_memory_bytes(&self) -> usize {
+        let vecs = self.offsets.capacity() + self.neighbors.capacity() + self.labels.inverse.capacity();
+        std::mem::size_of::<CsrGraph>()
+            + vecs * std::mem::size_of::<usize>()
+            + hash_table_bytes::<(Vertex, usize)>(self.labels.forward.capacity())
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.labels.len()
+    }
+
+    // neighbors of the node at `index`, as indices
+    pub fn neighbors(&self, index: usize) -> &[usize] {
+        &self.neighbors[self.offsets[index]..self.offsets[index + 1]]
+    }
+
+    pub fn degree(&self, index: usize) -> usize {
+        self.offsets[index + 1] - self.offsets[index]
+    }
+
+    // original id of the node at `index`
+    pub fn vertex(&self, index: usize) -> Vertex {
+        self.labels.to_original(index)
+    }
+
+    // index of an original vertex id, None if it isn't in the graph
+    pub fn index_of(&self, v: Vertex) -> Option<usize> {
+        self.labels.to_new(v)
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    // how the original ids map to the indices used here
+    pub fn relabeling(&self) -> &Relabeling {
+        &self.labels
+    }
+
+    // bfs distances from the node at index `start`, result[i] is the distance to node i (None if it can't be reached)
+    pub fn bfs_distances(&self, start: usize) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.num_nodes()];
+        let mut queue = VecDeque::from([start]);
+        distances[start] = Some(0);
+
+        while let Some(current) = queue.pop_front() {
+            let next = distances[current].map(|d| d + 1);
+            for &neighbor in self.neighbors(current) {
+                if distances[neighbor].is_none() {
+                    distances[neighbor] = next;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        distances
+    }
+
+    // indices of the nodes reachable from `start` in depth first order, same stack approach as graph::dfs
+    pub fn dfs(&self, start: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.num_nodes()];
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+
+        while let Some(node) = stack.pop() {
+            if !visited[node] {
+                visited[node] = true;
+                order.push(node);
+                stack.extend(self.neighbors(node).iter().rev()); //reversed so smaller indices get visited first
+            }
+        }
+
+        order
+    }
+
+    // connected components as lists of indices, like graph::connected_nodes but visited is a Vec<bool> instead of
+    // a HashSet since the indices are 0..n. components come out in order of their smallest index and each one is
+    // in depth first order. for a directed graph this only follows out-edges, so it's meant for undirected ones
+    // it's several times faster than connected_nodes on a big graph, examples/components_bench.rs times the two
+    // (cargo run --release --example components_bench)
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut visited = vec![false; self.num_nodes()];
+        let mut components = Vec::new();
+        let mut stack = Vec::new();
+
+        for start in 0..self.num_nodes() {
+            if visited[start] {
+                continue;
+            }
+            let mut component = Vec::new();
+            visited[start] = true;
+            stack.push(start);
+            while let Some(node) = stack.pop() {
+                component.push(node);
+                for &neighbor in self.neighbors(node) {
+                    if !visited[neighbor] { //marked when pushed so a node only goes on the stack once
+                        visited[neighbor] = true;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            components.push(component);
+        }
+
+        components
+    }
+}
+
+// converts the HashMap based graph, nodes with no edges are kept as nodes with no neighbors
+impl From<&Graph> for CsrGraph {
+    fn from(graph: &Graph) -> CsrGraph {
+        let edges: Vec<Edge> = graph.adjacency_list().iter().flat_map(|(&u, neighbors)| neighbors.iter().map(move |&v| (u, v))).collect();
+        //the adjacency list already has both ways of an undirected edge so no need to add them again
+        CsrGraph::build(Relabeling::new(graph.nodes().collect()), &edges, false, graph.direction())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{bfs_distances, estimated_memory_bytes};
+
+    #[test]
+    fn csr_matches_adjacency_list() {
+        let edges = vec![(10, 20), (20, 30), (30, 10), (30, 40), (50, 60), (20, 10)];
+        let csr = CsrGraph::from_edges(&edges, Direction::Undirected);
+        assert_eq!(csr.num_nodes(), 6);
+        let i = csr.index_of(30).unwrap();
+        let neighbors: Vec<Vertex> = csr.neighbors(i).iter().map(|&n| csr.vertex(n)).collect();
+        assert_eq!(neighbors, vec![10, 20, 40]);
+        assert_eq!(csr.degree(csr.index_of(10).unwrap()), 2); //20-10 was a repeat
+
+        let graph = Graph::from_edges(&edges);
+        assert_eq!(CsrGraph::from(&graph), csr);
+
+        let expected = bfs_distances(&graph, 10);
+        let distances = csr.bfs_distances(csr.index_of(10).unwrap());
+        for (index, distance) in distances.iter().enumerate() {
+            assert_eq!(*distance, expected.get(&csr.vertex(index)).copied());
+        }
+
+        let order: Vec<Vertex> = csr.dfs(csr.index_of(10).unwrap()).into_iter().map(|i| csr.vertex(i)).collect();
+        assert_eq!(order, vec![10, 20, 30, 40]);
+    }
+
+    #[test]
+    fn relabel_to_contiguous_ids() {
+        let edges = vec![(98343, 141493), (98343, 58736), (7, 98343)];
+        let (relabeled, original) = relabel(&edges);
+        assert_eq!(original, vec![7, 58736, 98343, 141493]);
+        assert_eq!(relabeled, vec![(2, 3), (2, 1), (0, 2)]);
+        for (&(u, v), &(a, b)) in edges.iter().zip(&relabeled) {
+            assert_eq!((original[a], original[b]), (u, v)); //translating back gives the original edges
+        }
+
+        let relabeling = Relabeling::from_edges(&edges);
+        assert_eq!(relabeling.to_new(58736), Some(1));
+        assert_eq!(relabeling.to_new(5), None);
+        assert_eq!(relabeling.to_original(3), 141493);
+    }
+
+    #[test]
+    fn csr_components_match_hashset_version() {
+        let edges = vec![(10, 20), (20, 30), (40, 50), (60, 60), (70, 50)];
+        let csr = CsrGraph::from_edges(&edges, Direction::Undirected);
+        let components: Vec<Vec<Vertex>> = csr
+            .connected_components()
+            .into_iter()
+            .map(|component| {
+                let mut nodes: Vec<Vertex> = component.into_iter().map(|i| csr.vertex(i)).collect();
+                nodes.sort_unstable();
+                nodes
+            })
+            .collect();
+        assert_eq!(components, vec![vec![10, 20, 30], vec![40, 50, 70], vec![60]]);
+    }
+
+    #[test]
+    fn csr_components_are_the_same_sets() {
+        // a sparse random graph has lots of small components, plus two nodes with no edges at all
+        let mut graph = Graph::from_edges(&crate::generators::erdos_renyi(500, 0.003, 2));
+        graph.add_node(1000);
+        graph.add_node(1001);
+        let csr = CsrGraph::from(&graph);
+
+        let sorted = |mut components: Vec<Vec<Vertex>>| {
+            components.iter_mut().for_each(|component| component.sort_unstable());
+            components.sort();
+            components
+        };
+        let from_csr = sorted(csr.connected_components().into_iter().map(|c| c.into_iter().map(|i| csr.vertex(i)).collect()).collect());
+        let from_hashsets = sorted(crate::graph::connected_nodes(&graph).into_iter().map(|c| c.into_iter().collect()).collect());
+        assert!(from_csr.len() > 10);
+        assert_eq!(from_csr, from_hashsets);
+    }
+
+    #[test]
+    fn csr_uses_less_memory() {
+        let edges: Vec<Edge> = (0..1000).flat_map(|v| [(v, v + 1), (v, v + 2)]).collect();
+        let csr = CsrGraph::from_edges(&edges, Direction::Undirected);
+        assert!(csr.estimated_memory_bytes() < estimated_memory_bytes(&Graph::from_edges(&edges)));
+    }
+
+    #[test]
+    fn csr_directed_and_isolated() {
+        let directed = CsrGraph::from_edges(&[(1, 2), (2, 3)], Direction::Directed);
+        assert_eq!(directed.neighbors(directed.index_of(2).unwrap()), &[2]);
+        assert!(directed.neighbors(directed.index_of(3).unwrap()).is_empty());
+
+        let mut adjacency_list = crate::graph::build_adjacency_list(&[(1, 2)]);
+        adjacency_list.insert(7, Default::default()); //a node with no edges
+        let csr = CsrGraph::from(&Graph::from_adjacency_list(adjacency_list));
+        assert_eq!(csr.num_nodes(), 3);
+        assert_eq!(csr.degree(csr.index_of(7).unwrap()), 0);
+        assert_eq!(csr.neighbors(csr.index_of(1).unwrap()), &[csr.index_of(2).unwrap()]);
+    }
+}
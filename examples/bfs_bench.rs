@@ -0,0 +1,42 @@
+// times graph::bfs_distances against CsrGraph::bfs_distances on the same graph
+// run with: cargo run --release --example bfs_bench -- [edge file] [number of bfs runs]
+// the edge file defaults to the twitch one and is read like main does (header line skipped)
+use std::env;
+use std::error::Error;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use ds210_project::csr::CsrGraph;
+use ds210_project::graph::{bfs_distances, Graph, Vertex};
+use ds210_project::io::{open_edge_file, read_edge_list_with_options, EdgeListOptions};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = env::args().skip(1);
+    let input = PathBuf::from(args.next().unwrap_or_else(|| "large_twitch_edges.csv".to_string()));
+    let runs: usize = args.next().map_or(Ok(10), |runs| runs.parse())?;
+
+    let options = EdgeListOptions { skip_header: true, ..Default::default() };
+    let edges = read_edge_list_with_options(open_edge_file(&input)?, &options)?.edges;
+    let graph = Graph::from_edges(&edges);
+    let csr = CsrGraph::from(&graph);
+
+    // the same starts for both, spread out over the sorted node ids
+    let mut nodes: Vec<Vertex> = graph.nodes().collect();
+    nodes.sort_unstable();
+    let starts: Vec<Vertex> = (0..runs).map(|i| nodes[i * nodes.len() / runs.max(1)]).collect();
+    println!("{} nodes, {} edges read, {} bfs runs", graph.num_nodes(), edges.len(), starts.len());
+
+    let timer = Instant::now();
+    let hashmap_reached: usize = starts.iter().map(|&start| bfs_distances(&graph, start).len()).sum();
+    println!("graph::bfs_distances: {:?}", timer.elapsed());
+
+    let timer = Instant::now();
+    let csr_reached: usize = starts
+        .iter()
+        .map(|&start| csr.bfs_distances(csr.index_of(start).unwrap()).iter().filter(|d| d.is_some()).count())
+        .sum();
+    println!("CsrGraph::bfs_distances: {:?}", timer.elapsed());
+
+    assert_eq!(hashmap_reached, csr_reached); //both should reach the same nodes
+    Ok(())
+}
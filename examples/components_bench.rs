@@ -0,0 +1,41 @@
+// times graph::connected_nodes (HashSet visited) against CsrGraph::connected_components (Vec<bool> visited)
+// on a sparse erdos-renyi graph, which has lots of components. building the graphs isn't counted
+// run with: cargo run --release --example components_bench -- [nodes] [average degree]
+use std::env;
+use std::error::Error;
+use std::time::Instant;
+
+use ds210_project::csr::CsrGraph;
+use ds210_project::generators::erdos_renyi;
+use ds210_project::graph::{connected_nodes, Graph, Vertex};
+
+// every component as a sorted list of nodes, and the list of them sorted too, so the two versions can be compared
+fn sorted(mut components: Vec<Vec<Vertex>>) -> Vec<Vec<Vertex>> {
+    components.iter_mut().for_each(|component| component.sort_unstable());
+    components.sort_unstable();
+    components
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = env::args().skip(1);
+    let n: usize = args.next().map_or(Ok(1_000_000), |n| n.parse())?;
+    let average_degree: f64 = args.next().map_or(Ok(1.5), |degree| degree.parse())?;
+
+    let graph = Graph::from_edges(&erdos_renyi(n, average_degree / n as f64, 1));
+    let csr = CsrGraph::from(&graph);
+    println!("erdos_renyi({}, {} / n, 1): {} nodes with edges", n, average_degree, graph.num_nodes());
+
+    let timer = Instant::now();
+    let from_hashsets = connected_nodes(&graph);
+    println!("graph::connected_nodes: {:?}", timer.elapsed());
+
+    let timer = Instant::now();
+    let from_csr = csr.connected_components();
+    println!("CsrGraph::connected_components: {:?}", timer.elapsed());
+
+    let from_hashsets = sorted(from_hashsets.into_iter().map(|component| component.into_iter().collect()).collect());
+    let from_csr = sorted(from_csr.into_iter().map(|component| component.into_iter().map(|i| csr.vertex(i)).collect()).collect());
+    assert_eq!(from_hashsets, from_csr); //the same components, not just the same number of them
+    println!("{} components, same in both", from_csr.len());
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::graph::{Edge, Vertex};
+
+// random G(n, p) graph: nodes 0..n and every pair gets an edge with probability p, the same seed always gives
+// the same edges. instead of flipping a coin for all n^2/2 pairs this jumps straight to the next pair that gets an
+// edge (the gap is geometric), so it's O(n + edges), source used: batagelj & brandes, "efficient generation of
+// large random networks" (2005). edges come out as (smaller, bigger). p <= 0 gives no edges and p >= 1 every pair.
+// nodes that end up with no edges aren't in the list, so Graph::from_edges won't have them
+pub fn erdos_renyi(n: usize, p: f64, seed: u64) -> Vec<Edge> {
+    let mut edges = Vec::new();
+    if p <= 0.0 || n < 2 {
+        return edges;
+    }
+    if p >= 1.0 {
+        for v in 1..n {
+            edges.extend((0..v).map(|w| (w, v)));
+        }
+        return edges;
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let log_q = (1.0 - p).ln();
+    if log_q == 0.0 {
+        return edges; //p is so small that 1 - p rounds to 1.0, the skip would be infinite
+    }
+    let (mut v, mut w): (Vertex, i64) = (1, -1);
+    while v < n {
+        let r: f64 = rng.gen();
+        w += 1 + ((1.0 - r).ln() / log_q).floor() as i64; //how many pairs to skip before the next edge
+        while w >= v as i64 && v < n {
+            w -= v as i64;
+            v += 1;
+        }
+        if v < n {
+            edges.push((w as Vertex, v));
+        }
+    }
+    edges
+}
+
+// scale-free graph by preferential attachment (barabasi-albert), closer to the twitch graph than G(n, p) since
+// a few nodes end up as big hubs. nodes 0..m start out, then each new node from m up to n links to m different
+// earlier nodes picked with probability proportional to their degree (the first new node just links to all m).
+// same approach as networkx: every edge end goes in a list and picking from it uniformly is picking by degree.
+// edges come out as (earlier node, new node) so the result has (n - m) * m edges, none if m is 0 or n <= m
+pub fn barabasi_albert(n: usize, m: usize, seed: u64) -> Vec<Edge> {
+    let mut edges = Vec::with_capacity(n.saturating_sub(m) * m);
+    if m == 0 || n <= m {
+        return edges;
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut ends: Vec<Vertex> = Vec::with_capacity(2 * edges.capacity()); //each node once per edge it's on
+    let mut targets: Vec<Vertex> = (0..m).collect();
+    for source in m..n {
+        for &target in &targets {
+            edges.push((target, source));
+            ends.push(target);
+        }
+        ends.extend(std::iter::repeat_n(source, m));
+
+        targets.clear();
+        while targets.len() < m {
+            let pick = ends[rng.gen_range(0..ends.len())];
+            if !targets.contains(&pick) { //m is small so a linear check is fine
+                targets.push(pick);
+            }
+        }
+        targets.sort_unstable();
+    }
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+    use crate::stats::degree_stats;
+    use std::collections::HashSet;
+
+    #[test]
+    fn erdos_renyi_is_reproducible() {
+        let edges = erdos_renyi(200, 0.05, 11);
+        assert_eq!(edges, erdos_renyi(200, 0.05, 11));
+        assert_ne!(edges, erdos_renyi(200, 0.05, 12));
+
+        let expected = 0.05 * 200.0 * 199.0 / 2.0; //995
+        assert!((edges.len() as f64 - expected).abs() < 150.0);
+        assert!(edges.iter().all(|&(u, v)| u < v && v < 200));
+        assert_eq!(edges.iter().collect::<HashSet<_>>().len(), edges.len()); //no repeats
+
+        assert!(erdos_renyi(50, 0.0, 1).is_empty());
+        assert!(erdos_renyi(50, 1e-20, 1).is_empty());
+        assert_eq!(erdos_renyi(5, 1.0, 1).len(), 10);
+    }
+
+    #[test]
+    fn barabasi_albert_has_hubs() {
+        let edges = barabasi_albert(2000, 3, 5);
+        assert_eq!(edges, barabasi_albert(2000, 3, 5));
+        assert_eq!(edges.len(), (2000 - 3) * 3);
+        assert!(edges.iter().all(|&(u, v)| u < v));
+        assert_eq!(edges.iter().collect::<HashSet<_>>().len(), edges.len());
+
+        let graph = Graph::from_edges(&edges);
+        let stats = degree_stats(&graph);
+        assert_eq!(stats.min, 3);
+        assert!(stats.max > 10 * stats.median as usize); //a few nodes get way more links than the typical one
+        assert!(barabasi_albert(3, 3, 1).is_empty());
+    }
+}
@@ -0,0 +1,1020 @@
+use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+
+use crate::components::weakly_connected_components;
+use crate::csr::Relabeling;
+use crate::union_find::UnionFind;
+
+pub type Vertex = usize; // represents a node in the graph
+pub type Edge = (Vertex, Vertex); // represents the edge between two nodes
+pub type WeightedEdge = (Vertex, Vertex, f64); // an edge with a weight on it, like (u, v, weight)
+pub type AdjacencyList = HashMap<Vertex, HashSet<Vertex>>;
+// the weighted version of AdjacencyList, same keys but each neighbor set is a neighbor -> weight of the edge to it map.
+// the unweighted stuff (Graph, bfs, components...) only uses AdjacencyList, and the weighted algorithms like
+// paths::dijkstra take this, so the plain path doesn't have to carry weights around. the neighbors of a node are
+// the same in both, keys() of the inner map here is the HashSet there (see drop_weights and with_unit_weights)
+pub type WeightedAdjacencyList = HashMap<Vertex, HashMap<Vertex, f64>>;
+
+// whether an edge (u, v) goes both ways or only from u to v, undirected is the default
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Direction {
+    Directed,
+    #[default]
+    Undirected,
+}
+
+// what to do with an edge like (5, 5) that goes from a node to itself. it puts the node in its own neighbor set,
+// which adds to its degree and throws off clustering and triangle counts since those assume there aren't any.
+// Drop is the default: the node is kept but the loop isn't. the plain builders (build_adjacency_list and friends)
+// still keep every edge as given, the policy only applies through build_adjacency_list_with_policy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SelfLoopPolicy {
+    Keep,
+    #[default]
+    Drop,
+    Error, // reject the edge list on the first self-loop
+}
+
+// this is when I build an adjacency list from the edges
+pub fn build_adjacency_list(edges: &[Edge]) -> AdjacencyList {
+    build_adjacency_list_with_direction(edges, Direction::Undirected)
+}
+
+// same as build_adjacency_list but (u, v) only means u -> v, like u follows v
+pub fn build_directed_adjacency_list(edges: &[Edge]) -> AdjacencyList {
+    build_adjacency_list_with_direction(edges, Direction::Directed)
+}
+
+pub fn build_adjacency_list_with_direction(edges: &[Edge], direction: Direction) -> AdjacencyList {
+    let mut adjacency_list: AdjacencyList = HashMap::new();
+
+    for &edge in edges {
+        insert_edge(&mut adjacency_list, edge, direction);
+    }
+
+    adjacency_list
+}
+
+// build_adjacency_list_with_direction with a choice of what happens to self-loops, see SelfLoopPolicy
+pub fn build_adjacency_list_with_policy(edges: &[Edge], direction: Direction, self_loops: SelfLoopPolicy) -> Result<AdjacencyList, Box<dyn Error>> {
+    let mut adjacency_list: AdjacencyList = HashMap::new();
+
+    for (index, &(u, v)) in edges.iter().enumerate() {
+        if u == v {
+            match self_loops {
+                SelfLoopPolicy::Keep => {}
+                SelfLoopPolicy::Drop => {
+                    adjacency_list.entry(u).or_default(); //still a node, just without the loop
+                    continue;
+                }
+                SelfLoopPolicy::Error => return Err(format!("edge {} is a self-loop on node {}", index, u).into()),
+            }
+        }
+        insert_edge(&mut adjacency_list, (u, v), direction);
+    }
+
+    Ok(adjacency_list)
+}
+
+// puts one edge into an adjacency list, shared with the streaming reader in io
+pub(crate) fn insert_edge(adjacency_list: &mut AdjacencyList, (u, v): Edge, direction: Direction) {
+    adjacency_list.entry(u).or_default().insert(v); //for the edges, this puts v in the set where u is   
+    let v_neighbors = adjacency_list.entry(v).or_default(); //v still gets an entry when directed so it counts as a node
+    if direction == Direction::Undirected {
+        v_neighbors.insert(u); //for the vertices, puts u in the set where v is
+    }
+}
+
+// how many times each undirected edge shows up in the list, since the adjacency list's HashSets only keep one copy.
+// (u, v) and (v, u) are the same edge and get counted under (smaller, bigger). anything above 1 is a duplicate row
+pub fn edge_multiplicities(edges: &[Edge]) -> HashMap<Edge, usize> {
+    let mut counts = HashMap::new();
+    for &(u, v) in edges {
+        *counts.entry((u.min(v), u.max(v))).or_insert(0) += 1;
+    }
+    counts
+}
+
+// builds an undirected adjacency list that remembers the weight of every edge
+// if the same edge shows up more than once the smallest weight is kept since that's the one a shortest path would use
+pub fn build_weighted_adjacency_list(edges: &[WeightedEdge]) -> WeightedAdjacencyList {
+    let mut adjacency_list: WeightedAdjacencyList = HashMap::new();
+
+    for &(u, v, weight) in edges {
+        for (a, b) in [(u, v), (v, u)] {
+            let current = adjacency_list.entry(a).or_default().entry(b).or_insert(weight);
+            *current = current.min(weight);
+        }
+    }
+
+    adjacency_list
+}
+
+// the same adjacency list without the weights
+pub fn drop_weights(weighted: &WeightedAdjacencyList) -> AdjacencyList {
+    weighted.iter().map(|(&node, neighbors)| (node, neighbors.keys().copied().collect())).collect()
+}
+
+// every edge gets weight 1.0, so dijkstra on this gives the same distances as bfs
+pub fn with_unit_weights(adjacency_list: &AdjacencyList) -> WeightedAdjacencyList {
+    adjacency_list.iter().map(|(&node, neighbors)| (node, neighbors.iter().map(|&v| (v, 1.0)).collect())).collect()
+}
+
+// the graph itself, it owns the adjacency list so extra info about the graph can be kept next to it later
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Graph {
+    adjacency_list: AdjacencyList,
+    direction: Direction,
+}
+
+impl Graph {
+    // builds the (undirected) graph straight from a list of edges
+    pub fn from_edges(edges: &[Edge]) -> Graph {
+        Graph::from_edges_with_direction(edges, Direction::Undirected)
+    }
+
+    pub fn from_edges_with_direction(edges: &[Edge], direction: Direction) -> Graph {
+        Graph::from_adjacency_list_with_direction(build_adjacency_list_with_direction(edges, direction), direction)
+    }
+
+    // from_edges_with_direction but self-loops are handled by the policy, so this can fail with SelfLoopPolicy::Error
+    pub fn from_edges_with_policy(edges: &[Edge], direction: Direction, self_loops: SelfLoopPolicy) -> Result<Graph, Box<dyn Error>> {
+        Ok(Graph::from_adjacency_list_with_direction(build_adjacency_list_with_policy(edges, direction, self_loops)?, direction))
+    }
+
+    // wraps an adjacency list that was already built, treated as undirected
+    pub fn from_adjacency_list(adjacency_list: AdjacencyList) -> Graph {
+        Graph::from_adjacency_list_with_direction(adjacency_list, Direction::Undirected)
+    }
+
+    pub fn from_adjacency_list_with_direction(adjacency_list: AdjacencyList, direction: Direction) -> Graph {
+        Graph { adjacency_list, direction }
+    }
+
+    pub fn adjacency_list(&self) -> &AdjacencyList {
+        &self.adjacency_list
+    }
+
+    // gives the adjacency list back without copying it
+    pub fn into_adjacency_list(self) -> AdjacencyList {
+        self.adjacency_list
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.direction == Direction::Directed
+    }
+
+    // copy of the graph where every edge goes both ways
+    pub fn to_undirected(&self) -> Graph {
+        let mut adjacency_list = self.adjacency_list.clone();
+        for (&u, neighbors) in &self.adjacency_list {
+            for &v in neighbors {
+                adjacency_list.entry(v).or_default().insert(u);
+            }
+        }
+        Graph::from_adjacency_list(adjacency_list)
+    }
+
+    // the graph itself if it's already undirected, otherwise to_undirected. for the algorithms that ignore
+    // directions, so an undirected graph (like the twitch one) doesn't get copied for nothing
+    pub fn as_undirected(&self) -> Cow<'_, Graph> {
+        if self.is_directed() {
+            Cow::Owned(self.to_undirected())
+        } else {
+            Cow::Borrowed(self)
+        }
+    }
+
+    // goes through the neighbors of v (only the out-neighbors when directed), a node that isn't in the graph just has no neighbors
+    pub fn neighbors(&self, v: Vertex) -> impl Iterator<Item = Vertex> + '_ {
+        self.adjacency_list.get(&v).into_iter().flatten().copied()
+    }
+
+    // number of neighbors of v, for a directed graph this is the out-degree
+    pub fn degree(&self, v: Vertex) -> usize {
+        self.adjacency_list.get(&v).map_or(0, |neighbors| neighbors.len())
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.adjacency_list.len()
+    }
+
+    // goes through every node in the graph (in no particular order since it's a HashMap)
+    pub fn nodes(&self) -> impl Iterator<Item = Vertex> + '_ {
+        self.adjacency_list.keys().copied()
+    }
+
+    // all the nodes as a set, same as the free function vertices
+    pub fn vertices(&self) -> HashSet<Vertex> {
+        self.nodes().collect()
+    }
+
+    pub fn contains_node(&self, v: Vertex) -> bool {
+        self.adjacency_list.contains_key(&v)
+    }
+
+    pub fn has_edge(&self, u: Vertex, v: Vertex) -> bool {
+        self.adjacency_list.get(&u).is_some_and(|neighbors| neighbors.contains(&v))
+    }
+
+    // adds v with no edges, false if it was already there
+    pub fn add_node(&mut self, v: Vertex) -> bool {
+        if self.contains_node(v) {
+            return false;
+        }
+        self.adjacency_list.insert(v, HashSet::new());
+        true
+    }
+
+    // adds the edge (both ways when undirected) and any end that isn't a node yet, false if it was already there
+    pub fn add_edge(&mut self, u: Vertex, v: Vertex) -> bool {
+        let added = !self.has_edge(u, v);
+        insert_edge(&mut self.adjacency_list, (u, v), self.direction);
+        added
+    }
+
+    // takes the edge out (both ways when undirected), the nodes stay. false if there wasn't one
+    pub fn remove_edge(&mut self, u: Vertex, v: Vertex) -> bool {
+        let removed = self.adjacency_list.get_mut(&u).is_some_and(|neighbors| neighbors.remove(&v));
+        if removed && !self.is_directed() {
+            if let Some(neighbors) = self.adjacency_list.get_mut(&v) {
+                neighbors.remove(&u);
+            }
+        }
+        removed
+    }
+
+    // takes v out with every edge touching it, false if it wasn't in the graph.
+    // undirected only has to fix up v's neighbors, directed has to look through everything for edges into v
+    pub fn remove_node(&mut self, v: Vertex) -> bool {
+        if self.is_directed() {
+            let existed = self.contains_node(v);
+            remove_vertex(&mut self.adjacency_list, v);
+            return existed;
+        }
+        let Some(neighbors) = self.adjacency_list.remove(&v) else { return false };
+        for neighbor in neighbors {
+            if let Some(neighbor_set) = self.adjacency_list.get_mut(&neighbor) {
+                neighbor_set.remove(&v);
+            }
+        }
+        true
+    }
+}
+
+impl From<AdjacencyList> for Graph {
+    fn from(adjacency_list: AdjacencyList) -> Graph {
+        Graph::from_adjacency_list(adjacency_list)
+    }
+}
+
+// breadth first search used here: finds distances from start node to all the other nodes, source used: https://gist.github.com/vTurbine/16fbb99225ad4c0ac80b24855dd61a7c
+// the start doesn't get checked: one that isn't in the graph gets back just {start: 0}, the same as an isolated
+// node would. use checked_bfs_distances when start might not be a real node
+pub fn bfs_distances(graph: &Graph, start: Vertex) -> HashMap<Vertex, usize> {
+    let mut distances = HashMap::new(); //creates empty hashmap to store shortest distances, a node has been visited once it's in here
+    let mut queue = VecDeque::new(); //empty queue to use for going through the nodes in order
+
+    queue.push_back(start); //add starting node to queue
+    distances.insert(start, 0);
+
+    while let Some(current) = queue.pop_front() { //loop that goes until the queue is empty
+        let distance = distances[&current]; //everything in the queue already has a distance
+
+        for neighbor in graph.neighbors(current) { //loop goes through neighbors of the nodes in the adjacency list, leaf nodes don't allocate anything
+            if let Entry::Vacant(entry) = distances.entry(neighbor) { //checks if neighbor has been visited or not, one lookup instead of two
+                entry.insert(distance + 1);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    distances
+}
+
+// bfs_distances that errors when start isn't a node in the graph, so "no such node" (usually a bug in the
+// caller, like a typo'd id) can't be mistaken for an isolated node that just has nothing to reach
+pub fn checked_bfs_distances(graph: &Graph, start: Vertex) -> Result<HashMap<Vertex, usize>, Box<dyn Error>> {
+    if !graph.contains_node(start) {
+        return Err(format!("node {} isn't in the graph", start).into());
+    }
+    Ok(bfs_distances(graph, start))
+}
+
+// lazy version of bfs_distances: gives (node, distance) pairs in bfs order from start, one at a time, so
+// take() or find() can stop early without going through the whole component. start comes first with distance 0
+#[derive(Debug, Clone)]
+pub struct BfsIter<'a> {
+    graph: &'a Graph,
+    queue: VecDeque<(Vertex, usize)>,
+    visited: HashSet<Vertex>,
+}
+
+impl<'a> BfsIter<'a> {
+    pub fn new(graph: &'a Graph, start: Vertex) -> BfsIter<'a> {
+        BfsIter { graph, queue: VecDeque::from([(start, 0)]), visited: HashSet::from([start]) }
+    }
+}
+
+impl Iterator for BfsIter<'_> {
+    type Item = (Vertex, usize);
+
+    fn next(&mut self) -> Option<(Vertex, usize)> {
+        let (current, distance) = self.queue.pop_front()?;
+        for neighbor in self.graph.neighbors(current) {
+            if self.visited.insert(neighbor) {
+                self.queue.push_back((neighbor, distance + 1));
+            }
+        }
+        Some((current, distance))
+    }
+}
+
+// lazy depth first walk from start that gives every reachable node once, with the same stack approach as dfs
+#[derive(Debug, Clone)]
+pub struct DfsIter<'a> {
+    graph: &'a Graph,
+    stack: Vec<Vertex>,
+    visited: HashSet<Vertex>,
+}
+
+impl<'a> DfsIter<'a> {
+    pub fn new(graph: &'a Graph, start: Vertex) -> DfsIter<'a> {
+        DfsIter { graph, stack: vec![start], visited: HashSet::new() }
+    }
+}
+
+impl Iterator for DfsIter<'_> {
+    type Item = Vertex;
+
+    fn next(&mut self) -> Option<Vertex> {
+        while let Some(node) = self.stack.pop() {
+            if self.visited.insert(node) { //a node can be on the stack more than once, only the first pop counts
+                self.stack.extend(self.graph.neighbors(node).filter(|neighbor| !self.visited.contains(neighbor)));
+                return Some(node);
+            }
+        }
+        None
+    }
+}
+
+// how many nodes are exactly d hops from start at index d, so [1, 3, 9, ...] is start, then its 3 neighbors,
+// then 9 nodes two hops away. shows how fast the neighborhood grows (small world graphs blow up in a few steps)
+pub fn bfs_layer_sizes(graph: &Graph, start: Vertex) -> Vec<usize> {
+    let mut sizes = Vec::new();
+    for (_, distance) in BfsIter::new(graph, start) {
+        if distance == sizes.len() {
+            sizes.push(0); //bfs gives distances in order so a new layer always starts right after the last one
+        }
+        sizes[distance] += 1;
+    }
+    sizes
+}
+
+// bfs_distances that stops at max_depth hops from start, so "friends within 2 hops" doesn't have to go through
+// the whole giant component. nodes at max_depth are in the result but their neighbors don't get looked at
+pub fn bfs_distances_limited(graph: &Graph, start: Vertex, max_depth: usize) -> HashMap<Vertex, usize> {
+    let mut distances = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(current) = queue.pop_front() {
+        let distance = distances[&current];
+        if distance == max_depth {
+            continue; //at the limit, don't go any further from here
+        }
+        for neighbor in graph.neighbors(current) {
+            if let Entry::Vacant(entry) = distances.entry(neighbor) {
+                entry.insert(distance + 1);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    distances
+}
+
+// every node at most `hops` edges from start, start included (the ego network of start)
+pub fn neighborhood(graph: &Graph, start: Vertex, hops: usize) -> HashSet<Vertex> {
+    bfs_distances_limited(graph, start, hops).into_keys().collect()
+}
+
+// only the nodes exactly `hops` edges away, the ring at that distance. hops = 0 is just start
+pub fn neighborhood_at_exactly(graph: &Graph, start: Vertex, hops: usize) -> HashSet<Vertex> {
+    bfs_distances_limited(graph, start, hops)
+        .into_iter()
+        .filter(|&(_, distance)| distance == hops)
+        .map(|(v, _)| v)
+        .collect()
+}
+
+// bfs_distances but the distances go in a Vec indexed by the vertex itself, so there's no hashing at all.
+// meant for graphs whose vertices are already 0..n (see csr::relabel), for sparse ids the Vec gets as long
+// as the biggest id and the HashMap version is better. None means the vertex can't be reached (or isn't there)
+pub fn bfs_distances_vec(graph: &Graph, start: Vertex) -> Vec<Option<usize>> {
+    let len = graph.nodes().chain([start]).max().map_or(0, |max| max + 1);
+    let mut distances = vec![None; len];
+    let mut queue = VecDeque::from([start]);
+    distances[start] = Some(0);
+
+    while let Some(current) = queue.pop_front() {
+        let next = distances[current].map(|d| d + 1);
+        for neighbor in graph.neighbors(current) {
+            if distances[neighbor].is_none() {
+                distances[neighbor] = next;
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    distances
+}
+
+// this calculates the average degree of nodes in the graph
+pub fn average_degree(graph: &Graph) -> f64 { //Count the number of nodes in the graph
+    if graph.num_nodes() == 0 {
+        return 0.0; //0/0 would be NaN and that ends up in everything printed after it
+    }
+    let num_nodes = graph.num_nodes() as f64;
+    let total_degree: usize = graph.nodes().map(|node| graph.degree(node)).sum(); //for every node, find the number of neighbors (degree) and then sum it up (aka number of degrees = number of neighbors)
+    total_degree as f64 / num_nodes //divide by number of nodes to get the average
+}
+
+// how close the graph is to having every possible edge: 2E / (V(V-1)) undirected and E / (V(V-1)) directed,
+// so 0.0 is no edges and 1.0 is complete. 0.0 with fewer than 2 nodes since there's no possible edge then
+pub fn density(graph: &Graph) -> f64 {
+    let n = graph.num_nodes() as f64;
+    if graph.num_nodes() < 2 {
+        return 0.0;
+    }
+    let edges = num_edges(graph) as f64;
+    match graph.direction() {
+        Direction::Undirected => 2.0 * edges / (n * (n - 1.0)),
+        Direction::Directed => edges / (n * (n - 1.0)),
+    }
+}
+
+// number of edges. adding up the degrees counts every undirected edge from both ends so that gets halved,
+// except a self-loop is only in its node's set once so it's added back first. directed is just the sum
+pub fn num_edges(graph: &Graph) -> usize {
+    let total_degree: usize = graph.nodes().map(|node| graph.degree(node)).sum();
+    match graph.direction() {
+        Direction::Undirected => {
+            let self_loops = graph.nodes().filter(|&node| graph.has_edge(node, node)).count();
+            (total_degree + self_loops) / 2
+        }
+        Direction::Directed => total_degree,
+    }
+}
+
+// every node in the graph as a set, so callers don't have to rebuild it from the edge list each time.
+// the graph only knows about nodes that were in some edge (or got added with add_node), an isolated node that
+// never showed up needs its own list of every vertex, like connected_nodes_with_vertices takes
+pub fn vertices(graph: &Graph) -> HashSet<Vertex> {
+    graph.nodes().collect()
+}
+
+// the edge list back out of a graph, the opposite of build_adjacency_list. an undirected edge is in both nodes'
+// sets but only comes out once as (smaller, bigger), a directed edge comes out as (from, to). sorted so exporting
+// the same graph gives the same file. nodes with no edges don't make it into the list, see vertices
+pub fn edges(graph: &Graph) -> Vec<Edge> {
+    let mut edges: Vec<Edge> = graph
+        .nodes()
+        .flat_map(|u| graph.neighbors(u).map(move |v| (u, v)))
+        .filter(|&(u, v)| graph.is_directed() || u <= v)
+        .collect();
+    edges.sort_unstable();
+    edges
+}
+
+// depth-First Search (DFS)
+pub fn dfs(graph: &Graph, start: Vertex, visited: &mut HashSet<Vertex>, component: &mut HashSet<Vertex>) {
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() { //keep going through loop until no more nodes are left in the stack
+        if visited.insert(node) { //insert is false when it was already visited, source used: https://www.programiz.com/dsa/graph-dfs
+            component.insert(node);
+
+            for neighbor in graph.neighbors(node) { //go through the neighbors of the node in the graph, this source helped: https://codereview.stackexchange.com/questions/184046/dfs-implementation-in-rust
+                stack.push(neighbor); //push each of its unvisisted neighbors in the stack 
+            }
+        }
+    }
+}
+
+// use depth first search to find all the connected nodes in my graph 
+// for a directed graph the edges are followed both ways, so these are the weakly connected pieces
+// (see components::weakly_connected_components)
+pub fn connected_nodes(graph: &Graph) -> Vec<HashSet<Vertex>> { //ierates over nodes to see if its connected to anything 
+    if graph.is_directed() {
+        return weakly_connected_components(graph);
+    }
+
+    let mut visited = HashSet::new(); //a new HashSet called component to store the nodes belonging to the connected nodes
+    let mut components = Vec::new(); //collects connected nodes into empty vector 
+
+    for node in graph.nodes() {
+        if !visited.contains(&node) {
+            let mut component = HashSet::new();
+            dfs(graph, node, &mut visited, &mut component); //use dfs function here to visit nodes and check for connection
+            components.push(component);
+        }
+    }
+
+    components
+}
+
+// same components as connected_nodes but found with a union-find pass over the edges instead of dfs,
+// nothing recursive and no stack to grow, which is quicker on huge graphs. direction is ignored here too
+pub fn connected_nodes_union_find(graph: &Graph) -> Vec<HashSet<Vertex>> {
+    connected_nodes_with_vertices(graph, &[])
+}
+
+// connected components where `vertices` also count as nodes even if no edge touches them, so a node that
+// was never an edge endpoint (and isn't in the adjacency list) still shows up as a component of one.
+// pass the full vertex list when there is one to get the real number of components. repeats are fine,
+// and vertices that are already in the graph just go in their usual component. uses union-find like
+// connected_nodes_union_find
+pub fn connected_nodes_with_vertices(graph: &Graph, vertices: &[Vertex]) -> Vec<HashSet<Vertex>> {
+    let labels = Relabeling::new(graph.nodes().chain(vertices.iter().copied()).collect());
+    let mut sets = UnionFind::new(labels.len());
+    for (i, &v) in labels.inverse().iter().enumerate() {
+        for neighbor in graph.neighbors(v) {
+            sets.union(i, labels.to_new(neighbor).unwrap());
+        }
+    }
+
+    let mut by_root: HashMap<usize, HashSet<Vertex>> = HashMap::new();
+    for (i, &v) in labels.inverse().iter().enumerate() {
+        by_root.entry(sets.find(i)).or_default().insert(v);
+    }
+    by_root.into_values().collect()
+}
+
+// new graph with only the given vertices and the edges that have both ends in the set
+// vertices in the set that aren't in the graph are left out, and the direction carries over
+pub fn induced_subgraph(graph: &Graph, vertices: &HashSet<Vertex>) -> Graph {
+    let adjacency_list: AdjacencyList = graph
+        .adjacency_list()
+        .iter()
+        .filter(|(node, _)| vertices.contains(node))
+        .map(|(&node, neighbors)| (node, neighbors.intersection(vertices).copied().collect()))
+        .collect();
+    Graph::from_adjacency_list_with_direction(adjacency_list, graph.direction())
+}
+
+// takes v and every edge touching it out of the adjacency list, for things like seeing what happens to the
+// components when an influential node is gone. every neighbor set gets checked, not just v's neighbors,
+// so edges pointing at v in a directed list are removed too. nothing happens if v isn't there
+pub fn remove_vertex(graph: &mut AdjacencyList, v: Vertex) {
+    if graph.remove(&v).is_some() {
+        for neighbors in graph.values_mut() {
+            neighbors.remove(&v);
+        }
+    }
+}
+
+// same as remove_vertex but leaves the original alone and gives back a new adjacency list
+pub fn without_vertex(graph: &AdjacencyList, v: Vertex) -> AdjacencyList {
+    graph
+        .iter()
+        .filter(|&(&node, _)| node != v)
+        .map(|(&node, neighbors)| (node, neighbors.iter().copied().filter(|&w| w != v).collect()))
+        .collect()
+}
+
+// keeps only the nodes with min <= degree <= max (out-degree when directed), along with the edges between them.
+// taking nodes out lowers their neighbors' degrees, so after one pass some kept nodes can be under min.
+// with iterate = false it's one pass using the original degrees, with iterate = true the filter runs again on
+// its own result until nothing else gets dropped (with max = usize::MAX that's the same as structure::k_core)
+pub fn filter_by_degree(graph: &Graph, min: usize, max: usize, iterate: bool) -> AdjacencyList {
+    let mut current = graph.clone();
+    loop {
+        let keep: HashSet<Vertex> = current.nodes().filter(|&v| (min..=max).contains(&current.degree(v))).collect();
+        let done = keep.len() == current.num_nodes();
+        current = induced_subgraph(&current, &keep);
+        if done || !iterate {
+            return current.into_adjacency_list();
+        }
+    }
+}
+
+// every directed edge turned around, so u -> v becomes v -> u ("who follows me" instead of "who I follow").
+// every node stays in even if it ends up with no out-edges. an undirected graph comes back the same
+pub fn transpose(graph: &Graph) -> AdjacencyList {
+    if !graph.is_directed() {
+        return graph.adjacency_list().clone();
+    }
+    let mut reversed: AdjacencyList = graph.nodes().map(|v| (v, HashSet::new())).collect();
+    for u in graph.nodes() {
+        for v in graph.neighbors(u) {
+            reversed.get_mut(&v).unwrap().insert(u);
+        }
+    }
+    reversed
+}
+
+// bytes a hash table with room for `capacity` entries of type T takes, roughly. std's HashMap keeps its
+// buckets at most 7/8 full and each bucket is the entry itself plus one control byte
+pub(crate) fn hash_table_bytes<T>(capacity: usize) -> usize {
+    if capacity == 0 {
+        return 0;
+    }
+    let buckets = (capacity * 8 / 7).next_power_of_two();
+    buckets * (std::mem::size_of::<T>() + 1)
+}
+
+// rough size of the graph in memory: the outer HashMap's buckets (one (node, HashSet) entry each) plus every
+// neighbor set's buckets. it's a heuristic from the capacities, allocator overhead isn't counted, but it's close
+// enough to compare with csr::CsrGraph::estimated_memory_bytes and decide if it's worth switching.
+// for the full twitch graph this says about 216 MB, and about 115 MB for the CsrGraph of it
+pub fn estimated_memory_bytes(graph: &Graph) -> usize {
+    let adjacency_list = graph.adjacency_list();
+    let outer = hash_table_bytes::<(Vertex, HashSet<Vertex>)>(adjacency_list.capacity());
+    let inner: usize = adjacency_list.values().map(|neighbors| hash_table_bytes::<Vertex>(neighbors.capacity())).sum();
+    std::mem::size_of::<Graph>() + outer + inner
+}
+
+// above this many nodes complement prints a warning since the result can have up to V^2 edges
+pub const COMPLEMENT_WARN_NODES: usize = 5000;
+
+// the complement graph: an edge wherever the original doesn't have one (and no self-loops). all_nodes is the
+// set of nodes to use since the adjacency list doesn't know about nodes with no edges, repeats are fine and
+// graph nodes that aren't in all_nodes are left out. only practical for small or dense vertex sets, a sparse
+// graph like the twitch one turns into something with almost V^2 edges. directed graphs keep their direction
+pub fn complement(graph: &Graph, all_nodes: &[Vertex]) -> AdjacencyList {
+    let nodes: HashSet<Vertex> = all_nodes.iter().copied().collect();
+    if nodes.len() > COMPLEMENT_WARN_NODES {
+        eprintln!("warning: the complement of {} nodes can have up to {} edges", nodes.len(), nodes.len() * (nodes.len() - 1) / 2);
+    }
+
+    nodes
+        .iter()
+        .map(|&u| (u, nodes.iter().copied().filter(|&v| v != u && !graph.has_edge(u, v)).collect()))
+        .collect()
+}
+
+#[cfg(test)] //need to do cargo test on terminal to see the test results 
+mod tests {
+    use super::*;
+    use crate::sampling::pair_up_nodes;
+
+    pub fn run_tests1(graph: &Graph) {
+        // I create a small test node/edge list to see if my adjacency list, pairing, and bfs distance all work
+        let test_edges: Vec<Edge> = vec![(1, 2), (2, 3), (3, 4), (4, 5), (5, 6)]; 
+        let test_nodes: HashSet<Vertex> = test_edges.iter().flat_map(|&(u, v)| vec![u, v]).collect(); //puts unique nodes into hashset
+        let test_pairs = pair_up_nodes(test_nodes.into_iter().collect(), 5).unwrap(); //generates random pairs from my test list
+        let test_adjacency_list = build_adjacency_list(&test_edges);
+        println!("test my paired nodes: {:?}", test_pairs);
+        println!("test my adjacency list: {:?}", test_adjacency_list);
+
+        for &(start, end) in &test_pairs {
+            let distances = bfs_distances(graph, start);
+            let distance = distances.get(&end).cloned().unwrap_or(usize::MAX); //if there is no connection between the nodes, it will output the maxiumum value for usize which is 18446744073709551615
+            println!("test distance between {} and {}: {}", start, end, distance);
+        }
+    }
+
+    #[test] //this is test function that actually creates the adjacenyc list and then uses run_tests1 to do the actual tests
+    fn run_tests2() {
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5), (5, 6)]);
+        run_tests1(&graph);
+    }
+
+    #[test]
+    fn graph_methods() {
+        let graph = Graph::from_edges(&[(1, 2), (1, 3), (2, 3), (3, 4)]);
+        assert_eq!(graph.num_nodes(), 4);
+        assert_eq!(graph.degree(3), 3);
+        assert_eq!(graph.degree(99), 0); //node that isn't in the graph
+        let mut neighbors: Vec<Vertex> = graph.neighbors(1).collect();
+        neighbors.sort();
+        assert_eq!(neighbors, vec![2, 3]);
+        assert!(graph.has_edge(4, 3));
+        assert!(!graph.has_edge(1, 4));
+        assert_eq!(Graph::from(build_adjacency_list(&[(1, 2)])), Graph::from_edges(&[(1, 2)]));
+    }
+
+    #[test]
+    fn counting_edges() {
+        //a square with one diagonal is 5 edges, the repeat and the reversed copy don't add any
+        let edges = [(1, 2), (2, 3), (3, 4), (4, 1), (1, 3), (2, 1), (1, 2)];
+        assert_eq!(num_edges(&Graph::from_edges(&edges)), 5);
+        assert_eq!(num_edges(&Graph::from_edges_with_direction(&edges, Direction::Directed)), 6);
+        assert_eq!(num_edges(&Graph::from_edges(&[(1, 2), (3, 3)])), 2); //a self-loop is one edge
+        assert_eq!(num_edges(&Graph::default()), 0);
+    }
+
+    #[test]
+    fn vertex_sets() {
+        let graph = Graph::from_edges_with_direction(&[(1, 2), (2, 3), (5, 5)], Direction::Directed);
+        assert_eq!(vertices(&graph), HashSet::from([1, 2, 3, 5])); //3 only has an edge coming in
+        assert_eq!(graph.vertices(), vertices(&graph));
+        assert!(vertices(&Graph::default()).is_empty());
+    }
+
+    #[test]
+    fn edges_round_trip() {
+        let graph = Graph::from_edges(&[(2, 1), (2, 3), (3, 1), (1, 2), (4, 4), (5, 3)]);
+        let list = edges(&graph);
+        assert_eq!(list, vec![(1, 2), (1, 3), (2, 3), (3, 5), (4, 4)]);
+        assert_eq!(&build_adjacency_list(&list), graph.adjacency_list());
+        assert_eq!(list.len(), num_edges(&graph));
+
+        let directed = Graph::from_edges_with_direction(&[(2, 1), (1, 2), (3, 1)], Direction::Directed);
+        assert_eq!(edges(&directed), vec![(1, 2), (2, 1), (3, 1)]);
+        assert_eq!(&build_directed_adjacency_list(&edges(&directed)), directed.adjacency_list());
+    }
+
+    #[test]
+    fn average_degree_of_empty_graph() {
+        assert_eq!(average_degree(&Graph::default()), 0.0);
+        assert_eq!(average_degree(&Graph::from_edges(&[(1, 2), (2, 3)])), 4.0 / 3.0);
+    }
+
+    #[test]
+    fn density_of_small_graphs() {
+        let triangle = Graph::from_edges(&[(1, 2), (2, 3), (3, 1)]);
+        assert_eq!(density(&triangle), 1.0);
+        let path = Graph::from_edges(&[(1, 2), (2, 3), (3, 4)]);
+        assert_eq!(density(&path), 0.5);
+        let directed = Graph::from_edges_with_direction(&[(1, 2), (2, 3), (3, 1)], Direction::Directed);
+        assert_eq!(density(&directed), 0.5);
+        assert_eq!(density(&Graph::from_adjacency_list(AdjacencyList::from([(1, HashSet::new())]))), 0.0);
+        assert_eq!(density(&Graph::default()), 0.0);
+    }
+
+    #[test]
+    fn isolated_vertices_are_components() {
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (4, 5)]);
+        let mut components = connected_nodes_with_vertices(&graph, &[6, 1, 7, 6]);
+        components.sort_by_key(|component| *component.iter().min().unwrap());
+        let expected = vec![HashSet::from([1, 2, 3]), HashSet::from([4, 5]), HashSet::from([6]), HashSet::from([7])];
+        assert_eq!(components, expected);
+        assert_eq!(connected_nodes(&graph).len(), 2); //without the list 6 and 7 aren't known at all
+    }
+
+    #[test]
+    fn checked_bfs_tells_missing_from_isolated() {
+        let mut graph = Graph::from_edges(&[(1, 2)]);
+        graph.add_node(3);
+        assert_eq!(checked_bfs_distances(&graph, 1).unwrap(), bfs_distances(&graph, 1));
+        assert_eq!(checked_bfs_distances(&graph, 3).unwrap(), HashMap::from([(3, 0)]));
+        assert_eq!(checked_bfs_distances(&graph, 4).unwrap_err().to_string(), "node 4 isn't in the graph");
+        assert_eq!(bfs_distances(&graph, 4), HashMap::from([(4, 0)])); //unchecked looks just like the isolated node
+    }
+
+    #[test]
+    fn bfs_iter_goes_level_by_level() {
+        let graph = Graph::from_edges(&[(1, 2), (1, 3), (2, 4), (3, 4), (4, 5), (6, 7)]);
+        let visited: Vec<(Vertex, usize)> = BfsIter::new(&graph, 1).collect();
+        assert_eq!(visited.len(), 5);
+        assert_eq!(visited[0], (1, 0));
+        assert!(visited.windows(2).all(|pair| pair[0].1 <= pair[1].1)); //distances never go down
+        assert_eq!(visited.into_iter().collect::<HashMap<_, _>>(), bfs_distances(&graph, 1));
+
+        assert_eq!(BfsIter::new(&graph, 1).find(|&(_, d)| d == 2), Some((4, 2)));
+        assert_eq!(BfsIter::new(&graph, 1).take(1).count(), 1);
+    }
+
+    #[test]
+    fn dfs_iter_visits_each_node_once() {
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (6, 7)]);
+        let order: Vec<Vertex> = DfsIter::new(&graph, 1).collect();
+        assert_eq!(order[0], 1);
+        assert_eq!(order.len(), 5);
+        assert_eq!(order.iter().copied().collect::<HashSet<_>>(), HashSet::from([1, 2, 3, 4, 5]));
+
+        //on a path there's only one way to go so the order is fixed
+        let path = Graph::from_edges(&[(1, 2), (2, 3), (3, 4)]);
+        assert_eq!(DfsIter::new(&path, 2).collect::<Vec<_>>().len(), 4);
+        assert_eq!(DfsIter::new(&path, 1).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn layer_sizes() {
+        let graph = Graph::from_edges(&[(1, 2), (1, 3), (1, 4), (2, 5), (3, 5), (4, 6), (6, 7), (8, 9)]);
+        assert_eq!(bfs_layer_sizes(&graph, 1), vec![1, 3, 2, 1]);
+        assert_eq!(bfs_layer_sizes(&graph, 1).iter().sum::<usize>(), bfs_distances(&graph, 1).len());
+        assert_eq!(bfs_layer_sizes(&graph, 8), vec![1, 1]);
+    }
+
+    #[test]
+    fn limited_bfs_stops_at_max_depth() {
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5), (1, 6)]);
+        let distances = bfs_distances_limited(&graph, 1, 2);
+        assert_eq!(distances, HashMap::from([(1, 0), (2, 1), (6, 1), (3, 2)]));
+        assert!(distances.values().all(|&d| d <= 2));
+        assert_eq!(bfs_distances_limited(&graph, 1, 0), HashMap::from([(1, 0)]));
+        assert_eq!(bfs_distances_limited(&graph, 1, 10), bfs_distances(&graph, 1));
+    }
+
+    #[test]
+    fn neighborhoods() {
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 4), (1, 5), (5, 3), (6, 7)]);
+        assert_eq!(neighborhood(&graph, 1, 1), HashSet::from([1, 2, 5]));
+        assert_eq!(neighborhood(&graph, 1, 2), HashSet::from([1, 2, 3, 5]));
+        assert_eq!(neighborhood_at_exactly(&graph, 1, 2), HashSet::from([3]));
+        assert_eq!(neighborhood_at_exactly(&graph, 1, 0), HashSet::from([1]));
+        assert!(neighborhood_at_exactly(&graph, 1, 4).is_empty());
+    }
+
+    #[test]
+    fn vec_distances_match_hashmap() {
+        let graph = Graph::from_edges(&[(0, 1), (1, 2), (2, 3), (5, 6)]);
+        let distances = bfs_distances_vec(&graph, 0);
+        assert_eq!(distances, vec![Some(0), Some(1), Some(2), Some(3), None, None, None]);
+        for (v, d) in bfs_distances(&graph, 0) {
+            assert_eq!(distances[v], Some(d));
+        }
+    }
+
+    // every undirected edge has to be in both neighbor sets and point at a node that exists
+    fn assert_consistent(graph: &Graph) {
+        for u in graph.nodes() {
+            for v in graph.neighbors(u) {
+                assert!(graph.contains_node(v));
+                assert!(graph.is_directed() || graph.has_edge(v, u), "{} -> {} has no way back", u, v);
+            }
+        }
+    }
+
+    #[test]
+    fn adding_nodes_and_edges() {
+        let mut graph = Graph::default();
+        assert!(graph.add_node(7));
+        assert!(!graph.add_node(7));
+        assert!(graph.add_edge(1, 2));
+        assert!(graph.add_edge(2, 3));
+        assert!(!graph.add_edge(2, 1)); //same edge the other way around
+        let mut expected = Graph::from_edges(&[(1, 2), (2, 3)]);
+        expected.add_node(7);
+        assert_eq!(graph, expected);
+        assert_consistent(&graph);
+    }
+
+    #[test]
+    fn removing_edges_and_nodes() {
+        let mut graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4)]);
+        assert!(graph.remove_edge(3, 1));
+        assert!(!graph.remove_edge(1, 3));
+        assert!(!graph.has_edge(1, 3) && graph.contains_node(1));
+        assert!(graph.remove_node(3));
+        assert!(!graph.remove_node(3));
+        assert_eq!(graph.num_nodes(), 3);
+        assert_eq!(graph.degree(4), 0);
+        assert_consistent(&graph);
+
+        let mut directed = Graph::from_edges_with_direction(&[(1, 2), (2, 1), (3, 2)], Direction::Directed);
+        assert!(directed.remove_edge(1, 2));
+        assert!(directed.has_edge(2, 1)); //the other direction is its own edge
+        assert!(directed.remove_node(2));
+        assert_eq!(directed.degree(3), 0);
+        assert_consistent(&directed);
+    }
+
+    #[test]
+    fn removing_a_vertex() {
+        //a star around 1 plus 5-6, taking out the middle leaves every leaf on its own
+        let mut adjacency_list = build_adjacency_list(&[(1, 2), (1, 3), (1, 4), (5, 6)]);
+        let copy = without_vertex(&adjacency_list, 1);
+        remove_vertex(&mut adjacency_list, 1);
+        assert_eq!(adjacency_list, copy);
+        assert!(!adjacency_list.contains_key(&1));
+        assert!(adjacency_list.values().all(|neighbors| !neighbors.contains(&1)));
+        assert_eq!(connected_nodes(&Graph::from(adjacency_list)).len(), 4);
+
+        let mut directed = build_directed_adjacency_list(&[(2, 1), (1, 3)]);
+        remove_vertex(&mut directed, 1);
+        assert!(directed[&2].is_empty());
+    }
+
+    #[test]
+    fn weighted_and_unweighted_lists_line_up() {
+        let weighted = build_weighted_adjacency_list(&[(1, 2, 0.5), (2, 3, 2.0), (2, 1, 0.25)]);
+        assert_eq!(weighted[&1][&2], 0.25);
+        let unweighted = build_adjacency_list(&[(1, 2), (2, 3)]);
+        assert_eq!(drop_weights(&weighted), unweighted);
+        assert_eq!(drop_weights(&with_unit_weights(&unweighted)), unweighted);
+        assert!(with_unit_weights(&unweighted).values().flat_map(|n| n.values()).all(|&w| w == 1.0));
+    }
+
+    #[test]
+    fn parallel_edges_are_counted() {
+        let edges = [(1, 2), (2, 1), (1, 2), (2, 3), (4, 4)];
+        let counts = edge_multiplicities(&edges);
+        assert_eq!(counts, HashMap::from([((1, 2), 3), ((2, 3), 1), ((4, 4), 1)]));
+        assert_eq!(build_adjacency_list(&edges)[&1].len(), 1); //the adjacency list still only has it once
+    }
+
+    #[test]
+    fn self_loop_policies() {
+        let edges = [(1, 2), (2, 2), (3, 3)];
+        let kept = build_adjacency_list_with_policy(&edges, Direction::Undirected, SelfLoopPolicy::Keep).unwrap();
+        assert_eq!(kept, build_adjacency_list(&edges));
+        assert!(kept[&2].contains(&2));
+
+        let dropped = Graph::from_edges_with_policy(&edges, Direction::Undirected, SelfLoopPolicy::default()).unwrap();
+        assert_eq!(dropped.degree(2), 1);
+        assert!(dropped.contains_node(3) && dropped.degree(3) == 0);
+
+        let err = build_adjacency_list_with_policy(&edges, Direction::Directed, SelfLoopPolicy::Error).unwrap_err();
+        assert_eq!(err.to_string(), "edge 1 is a self-loop on node 2");
+    }
+
+    #[test]
+    fn directed_graph() {
+        let edges = vec![(1, 2), (2, 3), (4, 3)];
+        let adjacency_list = build_directed_adjacency_list(&edges);
+        assert!(adjacency_list[&1].contains(&2));
+        assert!(!adjacency_list[&2].contains(&1));
+        assert!(adjacency_list[&3].is_empty()); //3 has no out-edges but is still a node
+        assert_eq!(build_adjacency_list(&edges), build_adjacency_list_with_direction(&edges, Direction::default()));
+
+        let graph = Graph::from_edges_with_direction(&edges, Direction::Directed);
+        assert!(graph.is_directed());
+        assert_eq!(graph.num_nodes(), 4);
+        assert_eq!(graph.degree(3), 0);
+
+        let distances = bfs_distances(&graph, 1);
+        assert_eq!(distances.get(&3), Some(&2));
+        assert_eq!(distances.get(&4), None); //can't go backwards along 4 -> 3
+
+        let components = connected_nodes(&graph);
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].len(), 4);
+
+        assert!(matches!(graph.as_undirected(), Cow::Owned(ref undirected) if undirected.has_edge(3, 2)));
+        let undirected = Graph::from_edges(&edges);
+        assert!(matches!(undirected.as_undirected(), Cow::Borrowed(_))); //no copy when it's already undirected
+    }
+
+    #[test]
+    fn subgraph_drops_outside_edges() {
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4), (4, 5)]);
+        let subgraph = induced_subgraph(&graph, &HashSet::from([1, 2, 3, 99]));
+        assert_eq!(subgraph, Graph::from_edges(&[(1, 2), (2, 3), (3, 1)]));
+        assert_eq!(subgraph.degree(3), 2); //3-4 is gone
+    }
+
+    #[test]
+    fn degree_filtering() {
+        //a triangle 1-2-3 with 4 hanging off 3, a path 4-5-6, and a star around 10
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 6), (10, 11), (10, 12), (10, 13), (10, 14)]);
+        let once = filter_by_degree(&graph, 2, 3, false);
+        assert_eq!(once.keys().copied().collect::<HashSet<_>>(), HashSet::from([1, 2, 3, 4, 5]));
+        assert_eq!(once[&5], HashSet::from([4])); //5 is down to degree 1 after one pass
+
+        let stable = filter_by_degree(&graph, 2, 3, true);
+        assert_eq!(Graph::from(stable), Graph::from_edges(&[(1, 2), (2, 3), (3, 1)]));
+        assert!(filter_by_degree(&graph, 5, 10, true).is_empty());
+    }
+
+    #[test]
+    fn transpose_flips_directed_edges() {
+        let directed = Graph::from_edges_with_direction(&[(1, 2), (1, 3), (3, 2)], Direction::Directed);
+        let reversed = transpose(&directed);
+        assert_eq!(reversed, build_directed_adjacency_list(&[(2, 1), (3, 1), (2, 3)]));
+        assert!(reversed[&1].is_empty());
+        let twice = Graph::from_adjacency_list_with_direction(reversed, Direction::Directed);
+        assert_eq!(transpose(&twice), *directed.adjacency_list());
+
+        let undirected = Graph::from_edges(&[(1, 2), (2, 3)]);
+        assert_eq!(transpose(&undirected), *undirected.adjacency_list());
+    }
+
+    #[test]
+    fn memory_estimate_grows_with_the_graph() {
+        let small = Graph::from_edges(&[(1, 2)]);
+        let edges: Vec<Edge> = (0..1000).map(|v| (v, v + 1)).collect();
+        let big = Graph::from_edges(&edges);
+        assert!(estimated_memory_bytes(&Graph::default()) < estimated_memory_bytes(&small));
+        assert!(estimated_memory_bytes(&big) > 1000 * 2 * std::mem::size_of::<Vertex>()); //at least the 2000 neighbor entries
+    }
+
+    #[test]
+    fn complement_of_a_path() {
+        let graph = Graph::from_edges(&[(1, 2), (2, 3)]);
+        let flipped = complement(&graph, &[1, 2, 3, 4]); //4 isn't in the graph at all
+        assert_eq!(Graph::from(flipped), Graph::from_edges(&[(1, 3), (1, 4), (2, 4), (3, 4)]));
+
+        let directed = Graph::from_edges_with_direction(&[(1, 2)], Direction::Directed);
+        assert_eq!(complement(&directed, &[1, 2]), build_directed_adjacency_list(&[(2, 1)]));
+    }
+
+    #[test]
+    fn union_find_components_match_dfs() {
+        let graph = Graph::from_edges_with_direction(&[(1, 2), (3, 2), (4, 5), (6, 6), (7, 4)], Direction::Directed);
+        let sorted = |mut components: Vec<HashSet<Vertex>>| {
+            components.sort_by_key(|component| *component.iter().min().unwrap());
+            components
+        };
+        assert_eq!(sorted(connected_nodes_union_find(&graph)), sorted(connected_nodes(&graph)));
+        assert_eq!(connected_nodes_union_find(&graph).len(), 3);
+    }
+}
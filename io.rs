@@ -0,0 +1,600 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use crate::components::component_labels;
+use crate::graph::{insert_edge, AdjacencyList, Direction, Edge, Graph, Vertex, WeightedEdge};
+
+// reads "u,v" lines into edges. # comment lines and any line that doesn't start with two numbers (like a header) are skipped,
+// use read_edge_list_with_options to be explicit about the header instead
+pub fn read_edge_list<R: BufRead>(reader: R) -> Result<Vec<Edge>, Box<dyn Error>> {
+    read_edge_list_with_delimiter(reader, ',')
+}
+
+// opens an edge list file for any of the readers here. a path ending in .gz gets decompressed on the fly
+// when built with the gzip feature (and is an error without it) so the twitch file can stay gzipped on disk
+pub fn open_edge_file(path: &Path) -> Result<Box<dyn BufRead>, Box<dyn Error>> {
+    let file = File::open(path)?;
+    if path.extension().is_some_and(|extension| extension == "gz") {
+        #[cfg(feature = "gzip")]
+        return Ok(Box::new(BufReader::new(flate2::read::MultiGzDecoder::new(file))));
+        #[cfg(not(feature = "gzip"))]
+        return Err(format!("{} is gzipped, build with --features gzip to read it", path.display()).into());
+    }
+    Ok(Box::new(BufReader::new(file)))
+}
+
+// reads several edge list files (like shards of one big graph, opened with open_edge_file) and puts the edges together.
+// each file is read strictly with read_edge_list_with_options, so a line that isn't an edge is an error instead of
+// getting skipped, and skip_header drops the first line of every file like the twitch header. an edge that already
+// came up, in any file and either way around, isn't added again, so the order is the first time each edge was seen.
+// an error (can't open it, bad line) starts with the file it came from
+pub fn read_edge_lists(paths: &[&Path], skip_header: bool) -> Result<Vec<Edge>, Box<dyn Error>> {
+    let options = EdgeListOptions { skip_header, strict: true, ..Default::default() };
+    let mut seen: HashSet<Edge> = HashSet::new();
+    let mut edges = Vec::new();
+
+    for path in paths {
+        let reader = open_edge_file(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let parsed = read_edge_list_with_options(reader, &options).map_err(|e| format!("{}: {}", path.display(), e))?;
+        for (u, v) in parsed.edges {
+            if seen.insert((u.min(v), u.max(v))) {
+                edges.push((u, v));
+            }
+        }
+    }
+
+    Ok(edges)
+}
+
+// same as read_edge_list but for files split by something other than commas, like tabs
+pub fn read_edge_list_with_delimiter<R: BufRead>(reader: R, delimiter: char) -> Result<Vec<Edge>, Box<dyn Error>> {
+    let mut edge_list = Vec::new(); //creates an empty vector that will store the edges from the input
+
+    for line in reader.lines() { //loop that iterates over each line
+        let line = line?; //reads a line; if there's an issue, return an error https://stackoverflow.com/questions/30186037/how-can-i-read-a-single-line-from-stdin-in-rust
+        if is_comment(&line) {
+            continue;
+        }
+        if let Some(edge) = parse_edge(&line, Delimiter::Char(delimiter)) {
+            edge_list.push(edge); //if i get the nodes, I add it to the empty edge list
+        }
+    }
+
+    Ok(edge_list)
+}
+
+// reads the edges like read_edge_list but puts each one straight into an (undirected) adjacency list as it goes,
+// so the whole Vec of edges never has to be in memory next to the adjacency list. on the twitch file that's
+// 6.8 million edges less to hold at the peak. one line buffer gets reused for the whole file.
+// use read_edge_list and build_adjacency_list instead when the edge list itself is needed too
+pub fn build_adjacency_list_streaming<R: BufRead>(mut reader: R) -> Result<AdjacencyList, Box<dyn Error>> {
+    let mut adjacency_list = AdjacencyList::new();
+    let mut line = String::new();
+
+    while reader.read_line(&mut line)? > 0 {
+        if !is_comment(&line) {
+            if let Some(edge) = parse_edge(&line, Delimiter::default()) {
+                insert_edge(&mut adjacency_list, edge, Direction::Undirected);
+            }
+        }
+        line.clear();
+    }
+
+    Ok(adjacency_list)
+}
+
+// what separates the two nodes on a line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    Char(char), // one specific character, ',' for csv or '\t' for tsv
+    Whitespace, // any run of spaces and tabs, like the snap .txt files
+}
+
+impl Default for Delimiter {
+    fn default() -> Delimiter {
+        Delimiter::Char(',')
+    }
+}
+
+// degrees straight from the file in one pass without building the graph, it just counts how many times each node
+// shows up as an end of an edge. the only thing kept is one count per node so it's a lot less memory than the
+// adjacency list. since nothing remembers which edges were seen, a repeated edge counts twice and a self-loop adds 2
+// to its node, so this only matches graph.degree() when the file has neither (the twitch file doesn't).
+// the sum of the counts is 2 * the number of rows, and counts.len() is the number of nodes
+pub fn streaming_degree_counts<R: BufRead>(mut reader: R) -> Result<HashMap<Vertex, usize>, Box<dyn Error>> {
+    let mut counts: HashMap<Vertex, usize> = HashMap::new();
+    let mut line = String::new();
+
+    while reader.read_line(&mut line)? > 0 {
+        if !is_comment(&line) {
+            if let Some((u, v)) = parse_edge(&line, Delimiter::default()) {
+                *counts.entry(u).or_insert(0) += 1;
+                *counts.entry(v).or_insert(0) += 1;
+            }
+        }
+        line.clear();
+    }
+
+    Ok(counts)
+}
+
+// lines starting with # are comments/metadata, like at the top of the snap files
+fn is_comment(line: &str) -> bool {
+    line.trim_start().starts_with('#')
+}
+
+// the two nodes at the start of a line, None if they aren't both numbers
+fn parse_edge(line: &str, delimiter: Delimiter) -> Option<Edge> {
+    match delimiter {
+        Delimiter::Char(c) => first_two_nodes(line.split(c)),
+        Delimiter::Whitespace => first_two_nodes(line.split_whitespace()),
+    }
+}
+
+fn first_two_nodes<'a>(fields: impl Iterator<Item = &'a str>) -> Option<Edge> {
+    let mut nodes = fields.map(|s| s.trim().parse::<Vertex>()); //clean up spaces 
+
+    match (nodes.next(), nodes.next()) { //want to get a nodes from the list 
+        (Some(Ok(u)), Some(Ok(v))) => Some((u, v)),
+        _ => None,
+    }
+}
+
+// settings for read_edge_list_with_options
+#[derive(Debug, Clone, Default)]
+pub struct EdgeListOptions {
+    pub skip_header: bool,    // drop the first line without looking at it, like numeric_id_1,numeric_id_2
+    pub strict: bool,         // error on the first line that isn't an edge instead of skipping it
+    pub delimiter: Delimiter, // comma unless set
+}
+
+// what read_edge_list_with_options read, plus which lines it had to skip (1-based line numbers)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedEdgeList {
+    pub edges: Vec<Edge>,
+    pub skipped_lines: Vec<usize>,
+}
+
+// like read_edge_list but the header is handled on purpose: with skip_header the first line is dropped,
+// and without it a first line that isn't an edge is an error instead of being quietly skipped.
+// other bad lines are skipped and their line numbers reported, or with strict they're an error too.
+// # comment lines are always ignored and don't count as the first line or as skipped
+pub fn read_edge_list_with_options<R: BufRead>(reader: R, options: &EdgeListOptions) -> Result<ParsedEdgeList, Box<dyn Error>> {
+    let mut parsed = ParsedEdgeList::default();
+    let mut first_line = true; //the first line that isn't a comment, that's where a header would be
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line_number = index + 1;
+        if is_comment(&line) {
+            continue;
+        }
+        let is_first_line = std::mem::replace(&mut first_line, false);
+        if is_first_line && options.skip_header {
+            continue;
+        }
+        match parse_edge(&line, options.delimiter) {
+            Some(edge) => parsed.edges.push(edge),
+            None if options.strict || is_first_line => {
+                return Err(format!("line {} isn't an edge: {:?}", line_number, line).into())
+            }
+            None => parsed.skipped_lines.push(line_number),
+        }
+    }
+
+    Ok(parsed)
+}
+
+// like read_edge_list but also reads a third column as the weight of the edge (u,v,weight)
+// if there's no third column the weight is 1.0, but a weight that isn't a number is an error. so are nan, inf
+// and negative weights, since dijkstra and the weighted betweenness assume 0 or more
+pub fn read_weighted_edge_list<R: BufRead>(reader: R) -> Result<Vec<WeightedEdge>, Box<dyn Error>> {
+    let mut edge_list = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if is_comment(&line) {
+            continue;
+        }
+        let mut fields = line.split(',').map(|s| s.trim());
+
+        let (u, v) = match (fields.next().map(str::parse::<Vertex>), fields.next().map(str::parse::<Vertex>)) {
+            (Some(Ok(u)), Some(Ok(v))) => (u, v),
+            _ => continue, //same as read_edge_list, lines without two nodes get skipped
+        };
+
+        let weight = match fields.next() {
+            None | Some("") => 1.0,
+            Some(field) => match field.parse::<f64>() {
+                Ok(weight) if weight.is_finite() && weight >= 0.0 => weight,
+                Ok(_) => return Err(format!("line {}: bad weight {:?}: has to be a finite number >= 0", index + 1, field).into()),
+                Err(e) => return Err(format!("line {}: bad weight {:?}: {}", index + 1, field, e).into()),
+            },
+        };
+        edge_list.push((u, v, weight));
+    }
+
+    Ok(edge_list)
+}
+
+// reads a 0/1 adjacency matrix, one comma separated row per node, where row i column j = 1 means an edge i -> j
+// nodes are numbered by row, so a row of all zeros is still a node (with no neighbors).
+// the matrix has to be square, and with check_symmetric it also has to match its transpose (an undirected graph)
+pub fn read_adjacency_matrix<R: BufRead>(reader: R, check_symmetric: bool) -> Result<AdjacencyList, Box<dyn Error>> {
+    let mut rows: Vec<Vec<bool>> = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let row = line
+            .split(',')
+            .map(|entry| match entry.trim() {
+                "0" => Ok(false),
+                "1" => Ok(true),
+                other => Err(format!("line {}: matrix entries have to be 0 or 1, got {:?}", index + 1, other)),
+            })
+            .collect::<Result<Vec<bool>, String>>()?;
+        rows.push(row);
+    }
+
+    let n = rows.len();
+    if let Some((i, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != n) {
+        return Err(format!("matrix isn't square: {} rows but row {} has {} entries", n, i, row.len()).into());
+    }
+    if check_symmetric {
+        for (i, row) in rows.iter().enumerate() {
+            for j in (i + 1)..n {
+                if row[j] != rows[j][i] {
+                    return Err(format!("matrix isn't symmetric: entry ({}, {}) doesn't match ({}, {})", i, j, j, i).into());
+                }
+            }
+        }
+    }
+
+    let adjacency_list = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| (i, row.iter().enumerate().filter(|(_, &entry)| entry).map(|(j, _)| j).collect()))
+        .collect();
+    Ok(adjacency_list)
+}
+
+// writes the (start, end, distance) results as a csv with a start,end,distance header
+// pairs with no path (distance None) get `inf` so it's clear they can't reach each other
+pub fn write_distances_csv<W: Write>(writer: W, pairs: &[(Vertex, Vertex, Option<usize>)]) -> Result<(), Box<dyn Error>> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+    csv_writer.write_record(["start", "end", "distance"])?;
+
+    for &(start, end, distance) in pairs {
+        let distance = distance.map_or("inf".to_string(), |d| d.to_string());
+        csv_writer.write_record([start.to_string(), end.to_string(), distance])?;
+    }
+
+    csv_writer.flush()?;
+    Ok(())
+}
+
+// write_distances_csv with more columns so the file makes sense on its own: whether the pair is reachable,
+// the component id of each end (from components::component_labels) and how big those components are.
+// an end that isn't in the graph gets empty component columns
+pub fn write_distances_csv_with_components<W: Write>(
+    writer: W,
+    pairs: &[(Vertex, Vertex, Option<usize>)],
+    graph: &Graph,
+) -> Result<(), Box<dyn Error>> {
+    let labels = component_labels(graph);
+    let mut sizes = vec![0; labels.values().max().map_or(0, |&id| id + 1)];
+    for &id in labels.values() {
+        sizes[id] += 1;
+    }
+    let component = |v: Vertex| labels.get(&v).map_or((String::new(), String::new()), |&id| (id.to_string(), sizes[id].to_string()));
+
+    let mut csv_writer = csv::Writer::from_writer(writer);
+    csv_writer.write_record([
+        "start",
+        "end",
+        "distance",
+        "reachable",
+        "start_component",
+        "end_component",
+        "start_component_size",
+        "end_component_size",
+    ])?;
+
+    for &(start, end, distance) in pairs {
+        let (start_component, start_size) = component(start);
+        let (end_component, end_size) = component(end);
+        csv_writer.write_record([
+            start.to_string(),
+            end.to_string(),
+            distance.map_or("inf".to_string(), |d| d.to_string()),
+            distance.is_some().to_string(),
+            start_component,
+            end_component,
+            start_size,
+            end_size,
+        ])?;
+    }
+
+    csv_writer.flush()?;
+    Ok(())
+}
+
+// writes the graph in graphviz dot format so it can be drawn with `dot -Tpng`
+// undirected graphs become `graph G { ... }` with each edge once, directed ones `digraph G { ... }`.
+// nodes with no edges get their own line so they still show up. everything is sorted so the output is the same every time
+pub fn write_dot<W: Write>(graph: &Graph, mut writer: W) -> io::Result<()> {
+    let (keyword, connector) = if graph.is_directed() { ("digraph", "->") } else { ("graph", "--") };
+    writeln!(writer, "{} G {{", keyword)?;
+
+    let mut nodes: Vec<Vertex> = graph.nodes().collect();
+    nodes.sort_unstable();
+    for &u in &nodes {
+        let mut neighbors: Vec<Vertex> = graph.neighbors(u).filter(|&v| graph.is_directed() || u <= v).collect();
+        neighbors.sort_unstable();
+        if graph.degree(u) == 0 {
+            writeln!(writer, "    {};", u)?;
+        }
+        for v in neighbors {
+            writeln!(writer, "    {} {} {};", u, connector, v)?;
+        }
+    }
+
+    writeln!(writer, "}}")
+}
+
+// writes the graph as node-link json, the format d3.js and networkx (node_link_graph) read:
+// {"directed": .., "multigraph": false, "graph": {}, "nodes": [{"id": ..}], "links": [{"source": .., "target": ..}]}
+// like write_dot, undirected edges are only written once and everything is sorted
+#[cfg(feature = "json")]
+pub fn write_json<W: Write>(graph: &Graph, writer: W) -> io::Result<()> {
+    let mut nodes: Vec<Vertex> = graph.nodes().collect();
+    nodes.sort_unstable();
+
+    let mut links = Vec::new();
+    for &u in &nodes {
+        let mut neighbors: Vec<Vertex> = graph.neighbors(u).filter(|&v| graph.is_directed() || u <= v).collect();
+        neighbors.sort_unstable();
+        links.extend(neighbors.into_iter().map(|v| serde_json::json!({ "source": u, "target": v })));
+    }
+
+    let node_link = serde_json::json!({
+        "directed": graph.is_directed(),
+        "multigraph": false,
+        "graph": {},
+        "nodes": nodes.iter().map(|&id| serde_json::json!({ "id": id })).collect::<Vec<_>>(),
+        "links": links,
+    });
+    serde_json::to_writer(writer, &node_link)?;
+    Ok(())
+}
+
+// the two json layouts read_json_edges understands, serde tries them in order
+#[cfg(feature = "json")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum JsonEdges {
+    Pairs(Vec<Edge>), // [[1, 2], [2, 3]]
+    NodeLink { links: Vec<JsonLink> }, // what write_json writes, only the links are needed
+}
+
+#[cfg(feature = "json")]
+#[derive(serde::Deserialize)]
+struct JsonLink {
+    source: Vertex,
+    target: Vertex,
+}
+
+// reads edges from json, either a plain array of [u, v] pairs or a node-link object like write_json makes
+// (which form it is gets detected). nodes in a node-link file with no links aren't edges so they're lost
+#[cfg(feature = "json")]
+pub fn read_json_edges<R: io::Read>(reader: R) -> Result<Vec<Edge>, Box<dyn Error>> {
+    let edges = match serde_json::from_reader(reader)? {
+        JsonEdges::Pairs(pairs) => pairs,
+        JsonEdges::NodeLink { links } => links.into_iter().map(|link| (link.source, link.target)).collect(),
+    };
+    Ok(edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{build_adjacency_list, Direction};
+
+    #[test]
+    fn header_handling() {
+        let input = "numeric_id_1,numeric_id_2\n1,2\n2,3\n";
+        let skip = EdgeListOptions { skip_header: true, ..Default::default() };
+        assert_eq!(read_edge_list_with_options(input.as_bytes(), &skip).unwrap().edges, vec![(1, 2), (2, 3)]);
+        assert!(read_edge_list_with_options(input.as_bytes(), &EdgeListOptions::default()).is_err());
+        assert_eq!(read_edge_list_with_options("1,2\n".as_bytes(), &EdgeListOptions::default()).unwrap().edges, vec![(1, 2)]);
+        assert_eq!(read_edge_list_with_options("1,2\n".as_bytes(), &skip).unwrap().edges, vec![]); //skipping means the first line is gone even if it's an edge
+    }
+
+    #[test]
+    fn skipped_lines_are_reported() {
+        let input = "u,v\n1,2\noops\n2,3\n4,\n";
+        let options = EdgeListOptions { skip_header: true, ..Default::default() };
+        let parsed = read_edge_list_with_options(input.as_bytes(), &options).unwrap();
+        assert_eq!(parsed.edges, vec![(1, 2), (2, 3)]);
+        assert_eq!(parsed.skipped_lines, vec![3, 5]);
+
+        let strict = EdgeListOptions { skip_header: true, strict: true, ..Default::default() };
+        let err = read_edge_list_with_options(input.as_bytes(), &strict).unwrap_err();
+        assert!(err.to_string().starts_with("line 3"));
+    }
+
+    #[test]
+    fn streaming_matches_two_step() {
+        let input = "numeric_id_1,numeric_id_2\n# comment\n1,2\n2,3\r\n3,1\n5,5\n";
+        let streamed = build_adjacency_list_streaming(input.as_bytes()).unwrap();
+        assert_eq!(streamed, build_adjacency_list(&read_edge_list(input.as_bytes()).unwrap()));
+        assert_eq!(streamed.len(), 4);
+    }
+
+    #[test]
+    fn streamed_degrees() {
+        let input = "numeric_id_1,numeric_id_2\n1,2\n2,3\n# comment\n3,1\n3,4\n";
+        let counts = streaming_degree_counts(input.as_bytes()).unwrap();
+        let graph = Graph::from_edges(&read_edge_list(input.as_bytes()).unwrap());
+        assert_eq!(counts.len(), graph.num_nodes());
+        assert!(graph.nodes().all(|v| counts[&v] == graph.degree(v)));
+
+        let repeats = streaming_degree_counts("1,2\n2,1\n5,5\n".as_bytes()).unwrap();
+        assert_eq!(repeats, HashMap::from([(1, 2), (2, 2), (5, 2)])); //nothing gets deduplicated
+    }
+
+    #[test]
+    fn merging_edge_files() {
+        let dir = std::env::temp_dir().join(format!("read_edge_lists_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (first, second) = (dir.join("part1.csv"), dir.join("part2.csv"));
+        std::fs::write(&first, "numeric_id_1,numeric_id_2\n1,2\n2,3\n").unwrap();
+        std::fs::write(&second, "numeric_id_1,numeric_id_2\n3,2\n3,4\n1,2\n").unwrap();
+
+        let edges = read_edge_lists(&[first.as_path(), second.as_path()], true).unwrap();
+        assert_eq!(edges, vec![(1, 2), (2, 3), (3, 4)]);
+
+        let missing = dir.join("missing.csv");
+        let err = read_edge_lists(&[first.as_path(), missing.as_path()], true).unwrap_err();
+        assert!(err.to_string().contains("missing.csv"));
+
+        let broken = dir.join("broken.csv");
+        std::fs::write(&broken, "numeric_id_1,numeric_id_2\n4,5\n5,oops\n").unwrap();
+        let err = read_edge_lists(&[first.as_path(), broken.as_path()], true).unwrap_err().to_string();
+        assert!(err.starts_with(&format!("{}: line 3", broken.display())), "{}", err);
+        assert!(read_edge_lists(&[first.as_path()], false).is_err()); //the header isn't an edge
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn gzipped_edge_file() {
+        use flate2::write::GzEncoder;
+
+        let path = std::env::temp_dir().join(format!("open_edge_file_{}.csv.gz", std::process::id()));
+        let mut encoder = GzEncoder::new(File::create(&path).unwrap(), flate2::Compression::default());
+        encoder.write_all(b"numeric_id_1,numeric_id_2\n1,2\n2,3\n").unwrap();
+        encoder.finish().unwrap();
+
+        let edges = read_edge_list(open_edge_file(&path).unwrap()).unwrap();
+        assert_eq!(edges, vec![(1, 2), (2, 3)]);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn other_delimiters() {
+        assert_eq!(read_edge_list_with_delimiter("1\t2\n3\t4\n".as_bytes(), '\t').unwrap(), vec![(1, 2), (3, 4)]);
+        let options = EdgeListOptions { delimiter: Delimiter::Whitespace, ..Default::default() };
+        let parsed = read_edge_list_with_options("1   2\n3 \t 4\n".as_bytes(), &options).unwrap();
+        assert_eq!(parsed.edges, vec![(1, 2), (3, 4)]);
+    }
+
+    #[test]
+    fn comment_lines_are_ignored() {
+        let input = "# Directed graph: web-Google.txt\n  # FromNodeId\tToNodeId\n1\t2\n2\t3\n";
+        let strict = EdgeListOptions { strict: true, delimiter: Delimiter::Whitespace, ..Default::default() };
+        let parsed = read_edge_list_with_options(input.as_bytes(), &strict).unwrap();
+        assert_eq!(parsed.edges, vec![(1, 2), (2, 3)]);
+        assert!(parsed.skipped_lines.is_empty());
+
+        let with_header = "# made by hand\nu,v\n1,2\n";
+        let options = EdgeListOptions { skip_header: true, strict: true, ..Default::default() };
+        assert_eq!(read_edge_list_with_options(with_header.as_bytes(), &options).unwrap().edges, vec![(1, 2)]);
+        assert_eq!(read_edge_list("#1,2\n3,4\n".as_bytes()).unwrap(), vec![(3, 4)]);
+    }
+
+    #[test]
+    fn adjacency_matrix() {
+        let input = "0,1,0\n1,0,1\n0,1,0\n";
+        let adjacency_list = read_adjacency_matrix(input.as_bytes(), true).unwrap();
+        assert_eq!(adjacency_list, build_adjacency_list(&[(0, 1), (1, 2)]));
+
+        let one_way = "0,1\n0,0\n";
+        assert!(read_adjacency_matrix(one_way.as_bytes(), true).is_err());
+        assert_eq!(read_adjacency_matrix(one_way.as_bytes(), false).unwrap()[&0].len(), 1);
+
+        assert!(read_adjacency_matrix("0,1\n1,0,0\n".as_bytes(), false).is_err()); //not square
+        assert!(read_adjacency_matrix("0,2\n2,0\n".as_bytes(), false).is_err());
+    }
+
+    #[test]
+    fn dot_output() {
+        let mut output = Vec::new();
+        write_dot(&Graph::from_edges(&[(2, 1), (2, 3), (1, 2)]), &mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "graph G {\n    1 -- 2;\n    2 -- 3;\n}\n");
+
+        let mut output = Vec::new();
+        write_dot(&Graph::from_edges_with_direction(&[(2, 1), (1, 2), (3, 1)], Direction::Directed), &mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "digraph G {\n    1 -> 2;\n    2 -> 1;\n    3 -> 1;\n}\n");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_output() {
+        let mut output = Vec::new();
+        write_json(&Graph::from_edges(&[(2, 1), (2, 3)]), &mut output).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(value["directed"], false);
+        assert_eq!(value["nodes"], serde_json::json!([{ "id": 1 }, { "id": 2 }, { "id": 3 }]));
+        assert_eq!(value["links"], serde_json::json!([{ "source": 1, "target": 2 }, { "source": 2, "target": 3 }]));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_edges() {
+        assert_eq!(read_json_edges("[[1, 2], [2, 3]]".as_bytes()).unwrap(), vec![(1, 2), (2, 3)]);
+
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 1)]);
+        let mut output = Vec::new();
+        write_json(&graph, &mut output).unwrap();
+        assert_eq!(Graph::from_edges(&read_json_edges(output.as_slice()).unwrap()), graph); //round trip
+
+        assert!(read_json_edges(r#"{"nodes": []}"#.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn reads_weights() {
+        let input = "u,v,weight\n1,2,0.5\n2,3\n3,4,\n";
+        let edges = read_weighted_edge_list(input.as_bytes()).unwrap();
+        assert_eq!(edges, vec![(1, 2, 0.5), (2, 3, 1.0), (3, 4, 1.0)]);
+    }
+
+    #[test]
+    fn bad_weight_is_an_error() {
+        let input = "1,2,0.5\n2,3,heavy\n";
+        let err = read_weighted_edge_list(input.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+
+        for weight in ["nan", "inf", "-inf", "-0.5"] {
+            let input = format!("1,2,0.5\n2,3,{}\n", weight);
+            let err = read_weighted_edge_list(input.as_bytes()).unwrap_err().to_string();
+            assert!(err.contains("line 2: bad weight"), "{}", err);
+        }
+        assert_eq!(read_weighted_edge_list("1,2,0\n".as_bytes()).unwrap(), vec![(1, 2, 0.0)]);
+    }
+
+    #[test]
+    fn distances_csv() {
+        let mut output = Vec::new();
+        write_distances_csv(&mut output, &[(1, 2, Some(3)), (4, 5, None)]).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "start,end,distance\n1,2,3\n4,5,inf\n");
+    }
+
+    #[test]
+    fn distances_csv_with_components() {
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (4, 5)]);
+        let mut output = Vec::new();
+        write_distances_csv_with_components(&mut output, &[(1, 3, Some(2)), (3, 4, None), (5, 99, None)], &graph).unwrap();
+        let expected = "start,end,distance,reachable,start_component,end_component,start_component_size,end_component_size\n\
+                        1,3,2,true,0,0,3,3\n\
+                        3,4,inf,false,0,1,3,2\n\
+                        5,99,inf,false,1,,2,\n";
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+    }
+}
@@ -0,0 +1,31 @@
+// library side of the project so the pieces can be used on their own:
+// - `centrality`: which nodes are the most important
+// - `clustering`: clustering coefficients
+// - `community`: finding communities (groups of nodes that link to each other a lot)
+// - `components`: more on the connected components, plus strongly connected ones for directed graphs
+// - `csr`: a compact graph representation for big graphs, and relabeling vertices to 0..n
+// - `generators`: random graphs for testing things without the real data
+// - `graph`: the graph types, building the adjacency list, bfs/dfs and connected nodes
+// - `io`: reading edge lists and writing out results
+// - `paths`: shortest paths beyond plain bfs distances
+// - `sampling`: randomly pairing up nodes
+// - `similarity`: how alike two nodes' neighborhoods are, for guessing links
+// - `spanning`: minimum spanning trees of weighted graphs
+// - `stats`: degree statistics of the graph
+// - `structure`: structural properties like being bipartite, k-cores, cut vertices, bridges, cycles and topological order
+// - `union_find`: a disjoint-set structure the other algorithms share
+pub mod centrality;
+pub mod clustering;
+pub mod community;
+pub mod components;
+pub mod csr;
+pub mod generators;
+pub mod graph;
+pub mod io;
+pub mod paths;
+pub mod sampling;
+pub mod similarity;
+pub mod spanning;
+pub mod stats;
+pub mod structure;
+pub mod union_find;
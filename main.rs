@@ -1,174 +1,175 @@
-use rand::seq::SliceRandom;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::HashMap;
 use std::error::Error;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-type Vertex = usize; // represents a node in the graph
-type Edge = (Vertex, Vertex); // represents the edge between two nodes
-type AdjacencyList = HashMap<Vertex, HashSet<Vertex>>;
-
-fn read_edge_list<R: BufRead>(reader: R) -> Result<Vec<Edge>, Box<dyn Error>> {
-    let mut edge_list = Vec::new(); //creates an empty vector that will store the edges from the input
-
-    for line in reader.lines() { //loop that iterates over each line
-        let line = line?; //reads a line; if there's an issue, return an error https://stackoverflow.com/questions/30186037/how-can-i-read-a-single-line-from-stdin-in-rust
-        let mut nodes = line.split(',').map(|s| s.trim().parse::<Vertex>()); //split line using commas and clean up spaces 
-
-        if let (Some(Ok(u)), Some(Ok(v))) = (nodes.next(), nodes.next()) { //want to get a nodes from the list 
-            edge_list.push((u, v)); //if i get the nodes, I add it to the empty edge list
-        }
-    }
-
-    Ok(edge_list)
+use std::path::PathBuf;
+
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+
+use ds210_project::centrality::{betweenness_centrality, closeness_centrality, harmonic_centrality, pagerank, DEFAULT_DAMPING};
+use ds210_project::components::component_report;
+use ds210_project::graph::{average_degree, density, num_edges, vertices, Direction, Graph, SelfLoopPolicy, Vertex};
+use ds210_project::io::{open_edge_file, read_edge_list_with_options, write_distances_csv, write_distances_csv_with_components, EdgeListOptions};
+use ds210_project::paths::pair_distances_with_progress;
+use ds210_project::sampling::{pair_up_nodes, pair_up_nodes_seeded};
+use ds210_project::stats::{degree_stats, distance_stats, top_degree_nodes};
+
+// command line options, run with --help to see them
+#[derive(Parser, Debug)]
+#[command(about = "graph analysis of an edge list (the twitch graph by default)")]
+struct Args {
+    /// edge list csv to read
+    #[arg(long, global = true, default_value = "large_twitch_edges.csv")]
+    input: PathBuf,
+
+    /// the input doesn't start with a header line (the twitch file does)
+    #[arg(long, global = true)]
+    no_header: bool,
+
+    /// only print the summary numbers (averages, component count) and no per-pair lines or progress, for scripts
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    #[command(subcommand)]
+    command: Command,
 }
 
-// takes the list of nodes and pairs them up randomly 
-fn pair_up_nodes(nodes: Vec<Vertex>, num_pairs: usize) -> Vec<Edge> {
-    let mut rng = rand::thread_rng();
-    let mut pairs = Vec::new(); //empty vector to store the pairs of nodes
-
-    while pairs.len() < num_pairs {
-        let selected_nodes: Vec<Vertex> = nodes.choose_multiple(&mut rng, 2).cloned().collect(); //iterates over nodes and picks two random ones to form a pair, used this source: https://www.reddit.com/r/rust/comments/r4ovyl/how_to_choose_a_random_string_or_integer_from_a/ 
-        pairs.push((selected_nodes[0], selected_nodes[1])); //puts pairs into empty vector
-    }
-
-    pairs 
+// the analyses that can be run, each one only does its own work
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// shortest distances between random pairs of nodes
+    Distances {
+        /// how many random pairs of nodes to find the distance between
+        #[arg(long, default_value_t = 1000)]
+        pairs: usize,
+
+        /// seed for picking the pairs so a run can be repeated, random if not given
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// write the distances to this csv file instead of printing them
+        #[arg(long)]
+        output: Option<PathBuf>,
+
+        /// only write the start,end,distance columns to the csv, without the reachable and component ones
+        #[arg(long, requires = "output")]
+        minimal: bool,
+    },
+    /// the connected nodes (components) of the graph
+    Components,
+    /// average degree and other degree statistics
+    Degree,
+    /// the most central nodes
+    Centrality {
+        /// which centrality to rank the nodes by
+        #[arg(long, value_enum, default_value_t = Measure::Pagerank)]
+        measure: Measure,
+
+        /// how many of the top nodes to print
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
 }
 
-// this is when I build an adjacency list from the edges
-fn build_adjacency_list(edges: &[Edge]) -> AdjacencyList {
-    let mut adjacency_list: AdjacencyList = HashMap::new();
-
-    for &(u, v) in edges {
-        adjacency_list.entry(u).or_insert_with(HashSet::new).insert(v); //for the edges, this puts v in the set where u is   
-        adjacency_list.entry(v).or_insert_with(HashSet::new).insert(u); //for the vertices, puts v in the set where u is
-    }
-
-    adjacency_list
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Measure {
+    Pagerank,
+    Betweenness, // runs a bfs from every node so it's slow on the full graph
+    Closeness,   // same here
+    Harmonic,    // and here, but it doesn't need the fix for disconnected parts
 }
 
-// breadth first search used here: finds distances from start node to all the other nodes, source used: https://gist.github.com/vTurbine/16fbb99225ad4c0ac80b24855dd61a7c
-fn bfs_distances(graph: &AdjacencyList, start: Vertex) -> HashMap<Vertex, usize> {
-    let mut distances = HashMap::new(); //creates empty hashmap to store shortest distances
-    let mut queue = VecDeque::new(); //empty queue to use for going through the nodes in order
-    let mut visited = HashSet::new(); //empty hashset to keep track of nodes that we visited already
-
-    queue.push_back(start); //add starting node to queue
-    visited.insert(start);
-    distances.insert(start, 0);
-
-    while let Some(current) = queue.pop_front() { //loop that goes until the queue is empty
-        let distance = *distances.get(&current).unwrap_or(&0);
+// this part calls into the library (lib.rs) to get the output
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
 
-        for &neighbor in graph.get(&current).unwrap_or(&HashSet::new()) { //loop goes through neighbors of the nodes in the adjacency list
-            if !visited.contains(&neighbor) { //checks if neighbor has been visited or not
-                visited.insert(neighbor);
-                distances.insert(neighbor, distance + 1);
-                queue.push_back(neighbor);
+    // this reads my csv file
+    let reader = match open_edge_file(&args.input) { //.gz files work too with the gzip feature
+        Ok(reader) => reader,
+        Err(e) => Args::command()
+            .error(ErrorKind::Io, format!("can't open input file {}: {}", args.input.display(), e))
+            .exit(), //prints the error with the usage message
+    };
+    let options = EdgeListOptions { skip_header: !args.no_header, ..Default::default() }; //the twitch file starts with a numeric_id_1,numeric_id_2 header
+    let parsed = read_edge_list_with_options(reader, &options)?;
+    if !parsed.skipped_lines.is_empty() {
+        eprintln!("skipped {} lines that weren't edges, first one was line {}", parsed.skipped_lines.len(), parsed.skipped_lines[0]);
+    }
+    let edge_list = parsed.edges;
+    let graph = Graph::from_edges_with_policy(&edge_list, Direction::Undirected, SelfLoopPolicy::Drop)?; //a node following itself would only mess up the degrees
+
+    match args.command {
+        Command::Distances { pairs, seed, output, minimal } => run_distances(&graph, pairs, seed, output, minimal, args.quiet)?,
+        Command::Components if args.quiet => println!("connected components: {}", component_report(&graph).count),
+        Command::Components => println!("{}", component_report(&graph)), //the full sets are way too long to print for the twitch graph
+        Command::Degree => {
+            let avg_degree = average_degree(&graph);
+            println!("average degree: {}", avg_degree);
+            if args.quiet {
+                return Ok(());
             }
+            println!("{:?}", degree_stats(&graph));
+            println!("nodes: {}, edges: {}", graph.num_nodes(), num_edges(&graph));
+            println!("density: {}", density(&graph));
+            println!("highest degree nodes: {:?}", top_degree_nodes(&graph, 10));
         }
+        Command::Centrality { measure, top } => run_centrality(&graph, measure, top),
     }
 
-    distances
+    Ok(())
 }
 
-#[cfg(test)] //need to do cargo test on terminal to see the test results 
-mod tests {
-    use super::*;
-
-    pub fn run_tests1(graph: &AdjacencyList) {
-        // I create a small test node/edge list to see if my adjacency list, pairing, and bfs distance all work
-        let test_edges: Vec<Edge> = vec![(1, 2), (2, 3), (3, 4), (4, 5), (5, 6)]; 
-        let test_nodes: HashSet<Vertex> = test_edges.iter().flat_map(|&(u, v)| vec![u, v]).collect(); //puts unique nodes into hashset
-        let test_pairs = pair_up_nodes(test_nodes.into_iter().collect(), 5); //generates random pairs from my test list
-        let test_adjacency_list = build_adjacency_list(&test_edges);
-        println!("test my paired nodes: {:?}", test_pairs);
-        println!("test my adjacency list: {:?}", test_adjacency_list);
-
-        for &(start, end) in &test_pairs {
-            let distances = bfs_distances(graph, start);
-            let distance = distances.get(&end).cloned().unwrap_or(usize::MAX); //if there is no connection between the nodes, it will output the maxiumum value for usize which is 18446744073709551615
-            println!("test distance between {} and {}: {}", start, end, distance);
+// I have to many nodes and it takes to long get an output so by default it's only 1000 pairs because the rubric said I needed 1000 nodes minimum
+fn run_distances(graph: &Graph, num_pairs: usize, seed: Option<u64>, output: Option<PathBuf>, minimal: bool, quiet: bool) -> Result<(), Box<dyn Error>> {
+    let nodes = vertices(graph); //self-loops only get dropped as edges so this is still every node in the file
+    let pairs = match seed {
+        Some(seed) => pair_up_nodes_seeded(nodes.into_iter().collect(), num_pairs, seed)?,
+        None => pair_up_nodes(nodes.into_iter().collect(), num_pairs)?,
+    };
+
+    let report_every = (pairs.len() / 100).max(1); //about every 1% so stderr doesn't get flooded
+    let distances = pair_distances_with_progress(graph, &pairs, |done, total| { //bfs only runs once for each different start
+        if !quiet && (done % report_every == 0 || done == total) {
+            eprint!("\rdone {}/{} pairs", done, total);
+            if done == total {
+                eprintln!();
+            }
         }
-    }
-
-    #[test] //this is test function that actually creates the adjacenyc list and then uses run_tests1 to do the actual tests
-    fn run_tests2() {
-        let adjacency_list = build_adjacency_list(&vec![(1, 2), (2, 3), (3, 4), (4, 5), (5, 6)]);
-        run_tests1(&adjacency_list);
-    }
-}
-
-// this calculates the average degree of nodes in the graph
-fn average_degree(graph: &AdjacencyList) -> f64 { //Count the number of nodes in the graph
-    let num_nodes = graph.len() as f64;
-    let total_degree: usize = graph.values().map(|neighbors| neighbors.len()).sum(); //for every node, find the number of neighbors (degree) and then sum it up (aka number of degrees = number of neighbors)
-    total_degree as f64 / num_nodes //divide by number of nodes to get the average
-}
-
-// depth-First Search (DFS)
-fn dfs(graph: &AdjacencyList, start: Vertex, visited: &mut HashSet<Vertex>, component: &mut HashSet<Vertex>) {
-    let mut stack = vec![start];
-
-    while let Some(node) = stack.pop() { //keep going through loop until no more nodes are left in the stack
-        if !visited.contains(&node) {
-            visited.insert(node); //source used: https://www.programiz.com/dsa/graph-dfs
-            component.insert(node);
-
-            if let Some(neighbors) = graph.get(&node) { //check if there are neighbors for the node in the graph, this source helped: https://codereview.stackexchange.com/questions/184046/dfs-implementation-in-rust
-                for &neighbor in neighbors {
-                    stack.push(neighbor); //if there are neighbors, push each of its unvisisted neighbors in the stack 
+    });
+    match output {
+        Some(path) if minimal => write_distances_csv(File::create(path)?, &distances)?, //--output puts the distances in a csv instead
+        Some(path) => write_distances_csv_with_components(File::create(path)?, &distances, graph)?,
+        None if quiet => {}
+        None => {
+            for &(start, end, distance) in &distances {
+                match distance {
+                    Some(distance) => println!("Distance between {} and {}: {}", start, end, distance),
+                    None => println!("Distance between {} and {}: unreachable", start, end),
                 }
             }
         }
     }
-}
-
-// use depth first search to find all the connected nodes in my graph 
-fn connected_nodes(graph: &AdjacencyList) -> Vec<HashSet<Vertex>> { //ierates over nodes to see if its connected to anything 
-    let mut visited = HashSet::new(); //a new HashSet called component to store the nodes belonging to the connected nodes
-    let mut components = Vec::new(); //collects connected nodes into empty vector 
 
-    for &node in graph.keys() {
-        if !visited.contains(&node) {
-            let mut component = HashSet::new();
-            dfs(graph, node, &mut visited, &mut component); //use dfs function here to visit nodes and check for connection
-            components.push(component);
-        }
+    let stats = distance_stats(&distances); //only the reachable pairs count towards these
+    println!("{} reachable pairs, {} unreachable", stats.reachable, stats.unreachable);
+    if let (Some(mean), Some(median), Some(max)) = (stats.mean, stats.median, stats.max) {
+        println!("mean distance: {:.3}, median: {}, max: {}", mean, median, max);
     }
-
-    components
+    Ok(())
 }
 
-// this part implements everything above to get the output 
-fn main() -> Result<(), Box<dyn Error>> {
-    let file_path = "large_twitch_edges.csv";
-    let num_pairs_to_generate = 1000; //I have to many nodes and it takes to long get an output so I chose to only do 1000 pairs because the rubric said I needed 1000 nodes minimum
-
-    // this reads my csv file
-    let file = File::open(file_path)?;
-    let reader = BufReader::new(file);
-    let edge_list = read_edge_list(reader)?;
-
-    let nodes: HashSet<Vertex> = edge_list.iter().flat_map(|&(u, v)| vec![u, v]).collect();
-    let pairs = pair_up_nodes(nodes.into_iter().collect(), num_pairs_to_generate);
-    let adjacency_list = build_adjacency_list(&edge_list);
-
-    #[cfg(test)]
-    tests::run_tests1(&adjacency_list);
-
-    for &(start, end) in &pairs {
-        let distances = bfs_distances(&adjacency_list, start);
-        let distance = *distances.get(&end).unwrap_or(&usize::MAX);
-        println!("Distance between {} and {}: {}", start, end, distance);
+// prints the `top` nodes with the highest centrality
+fn run_centrality(graph: &Graph, measure: Measure, top: usize) {
+    let scores: HashMap<Vertex, f64> = match measure {
+        Measure::Pagerank => pagerank(graph, DEFAULT_DAMPING, 20),
+        Measure::Betweenness => betweenness_centrality(graph, true),
+        Measure::Closeness => closeness_centrality(graph),
+        Measure::Harmonic => harmonic_centrality(graph, true),
+    };
+
+    let mut ranked: Vec<(Vertex, f64)> = scores.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    for (node, score) in ranked.into_iter().take(top) {
+        println!("{}: {}", node, score);
     }
-
-    let components = connected_nodes(&adjacency_list);
-    println!("connected nodes: {:?}", components);
-
-    let avg_degree = average_degree(&adjacency_list);
-    println!("average distance: {}", avg_degree);
-
-    Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,492 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::error::Error;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::components::largest_component;
+use crate::csr::Relabeling;
+use crate::graph::{bfs_distances, Edge, Graph, Vertex, WeightedAdjacencyList};
+use crate::sampling::pair_up_nodes_seeded;
+
+// entry in the priority queue for dijkstra, the ordering is flipped so the BinaryHeap (a max heap) pops the smallest distance first
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct State {
+    pub(crate) distance: f64,
+    pub(crate) vertex: Vertex,
+}
+
+impl Eq for State {}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.total_cmp(&self.distance).then_with(|| other.vertex.cmp(&self.vertex))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// dijkstra's algorithm: shortest weighted distance from start to every node it can reach, source used: https://doc.rust-lang.org/std/collections/binary_heap/index.html
+// just like bfs_distances, nodes that can't be reached are left out of the map. weights are assumed to not be negative
+pub fn dijkstra(graph: &WeightedAdjacencyList, start: Vertex) -> HashMap<Vertex, f64> {
+    let mut distances = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    distances.insert(start, 0.0);
+    heap.push(State { distance: 0.0, vertex: start });
+
+    while let Some(State { distance, vertex }) = heap.pop() {
+        if distance > distances[&vertex] {
+            continue; //already found a shorter way to this node, this entry is stale
+        }
+
+        for (&neighbor, &weight) in graph.get(&vertex).into_iter().flatten() {
+            let next = distance + weight;
+            if distances.get(&neighbor).is_none_or(|&best| next < best) {
+                distances.insert(neighbor, next);
+                heap.push(State { distance: next, vertex: neighbor });
+            }
+        }
+    }
+
+    distances
+}
+
+// bfs from start that remembers where each node was reached from, then walks back from end to get the path
+// the path has both start and end in it, in order, and is None if end can't be reached
+pub fn bfs_path(graph: &Graph, start: Vertex, end: Vertex) -> Option<Vec<Vertex>> {
+    let mut predecessors: HashMap<Vertex, Vertex> = HashMap::new(); //node -> the node it was reached from
+    let mut queue = VecDeque::new();
+
+    predecessors.insert(start, start);
+    queue.push_back(start);
+
+    while let Some(current) = queue.pop_front() {
+        if current == end {
+            break; //bfs reaches end by a shortest path first so we can stop here
+        }
+        for neighbor in graph.neighbors(current) {
+            if let Entry::Vacant(entry) = predecessors.entry(neighbor) {
+                entry.insert(current);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    if !predecessors.contains_key(&end) {
+        return None;
+    }
+
+    let mut path = vec![end];
+    let mut current = end;
+    while current != start {
+        current = predecessors[&current];
+        path.push(current);
+    }
+    path.reverse();
+    Some(path)
+}
+
+// shortest distance from start to end with a bfs growing from both ends, one level at a time and always on the
+// side with the smaller frontier. it stops at the first level where the two searches touch, so it usually looks
+// at far fewer nodes than a full bfs_distances from start. None when end can't be reached.
+// the backwards search would need the in-edges on a directed graph, so there it's a normal bfs that stops at end
+pub fn bidirectional_bfs(graph: &Graph, start: Vertex, end: Vertex) -> Option<usize> {
+    if start == end || graph.is_directed() {
+        return bfs_path(graph, start, end).map(|path| path.len() - 1);
+    }
+
+    let (mut from_start, mut from_end) = (HashMap::from([(start, 0)]), HashMap::from([(end, 0)]));
+    let (mut frontier_start, mut frontier_end) = (vec![start], vec![end]);
+    while !frontier_start.is_empty() && !frontier_end.is_empty() {
+        let (frontier, distances, other_distances) = if frontier_start.len() <= frontier_end.len() {
+            (&mut frontier_start, &mut from_start, &from_end)
+        } else {
+            (&mut frontier_end, &mut from_end, &from_start)
+        };
+        let mut best: Option<usize> = None;
+        let mut next = Vec::new();
+        for node in frontier.drain(..) {
+            let distance = distances[&node] + 1;
+            for neighbor in graph.neighbors(node) {
+                if let Some(&other) = other_distances.get(&neighbor) {
+                    best = Some(best.map_or(distance + other, |b| b.min(distance + other))); //the whole level has to be checked for the shortest meeting
+                }
+                if let Entry::Vacant(entry) = distances.entry(neighbor) {
+                    entry.insert(distance);
+                    next.push(neighbor);
+                }
+            }
+        }
+        if best.is_some() {
+            return best;
+        }
+        *frontier = next;
+    }
+    None //one side ran out of nodes, so its whole component was searched without finding the other
+}
+
+// whether there's any path from u to v, using bidirectional_bfs so it doesn't need a full bfs from u
+pub fn are_connected(graph: &Graph, u: Vertex, v: Vertex) -> bool {
+    bidirectional_bfs(graph, u, v).is_some()
+}
+
+// runs bfs_distances once from each of the sources (repeats only get computed once) and keeps all the results
+// careful on a big graph: every result is a map with up to one entry per node
+pub fn bfs_all(graph: &Graph, sources: &[Vertex]) -> HashMap<Vertex, HashMap<Vertex, usize>> {
+    let mut results = HashMap::new();
+    for &source in sources {
+        results.entry(source).or_insert_with(|| bfs_distances(graph, source));
+    }
+    results
+}
+
+// same results as bfs_all, but the bfs for each source runs in parallel with rayon
+// every bfs only reads the graph so nothing needs to be locked
+#[cfg(feature = "parallel")]
+pub fn parallel_bfs_all(graph: &Graph, sources: &[Vertex]) -> HashMap<Vertex, HashMap<Vertex, usize>> {
+    let mut unique_sources = sources.to_vec();
+    unique_sources.sort_unstable();
+    unique_sources.dedup();
+    unique_sources
+        .into_par_iter()
+        .map(|source| (source, bfs_distances(graph, source)))
+        .collect()
+}
+
+// bfs distance from start to end, None if there is no path between them
+pub fn distance(graph: &Graph, start: Vertex, end: Vertex) -> Option<usize> {
+    bfs_distances(graph, start).get(&end).copied()
+}
+
+// distance between each (start, end) pair, None if there is no path between them
+// pairs are grouped by start so bfs only runs once per different start, and each bfs result is
+// thrown away once its pairs are done instead of keeping them all in memory like bfs_all does
+// timing note: on the full twitch graph (release build), 300 pairs that only use 30 different starts took
+// about 152s running bfs fresh for every pair and about 17s with this. for fully random pairs starts hardly
+// ever repeat so there isn't much to save there
+pub fn pair_distances(graph: &Graph, pairs: &[Edge]) -> Vec<(Vertex, Vertex, Option<usize>)> {
+    pair_distances_with_progress(graph, pairs, |_, _| {})
+}
+
+// pair_distances that calls on_progress(done, total) after each pair is finished so a long run can show
+// it's still going. pairs with the same start finish right after each other since they share one bfs
+pub fn pair_distances_with_progress(
+    graph: &Graph,
+    pairs: &[Edge],
+    mut on_progress: impl FnMut(usize, usize),
+) -> Vec<(Vertex, Vertex, Option<usize>)> {
+    let mut pairs_by_start: HashMap<Vertex, Vec<usize>> = HashMap::new(); //start -> positions of its pairs in `pairs`
+    for (index, &(start, _)) in pairs.iter().enumerate() {
+        pairs_by_start.entry(start).or_default().push(index);
+    }
+
+    let mut results = vec![(0, 0, None); pairs.len()];
+    let mut done = 0;
+    for (start, indices) in pairs_by_start {
+        let distances = bfs_distances(graph, start);
+        for index in indices {
+            let end = pairs[index].1;
+            results[index] = (start, end, distances.get(&end).copied());
+            done += 1;
+            on_progress(done, pairs.len());
+        }
+    }
+
+    results
+}
+
+// splits the results of pair_distances into the distances of the pairs that had a path and a count of the
+// ones that didn't, so an unreachable pair can't end up in an average as some huge number like usize::MAX
+pub fn split_reachable(distances: &[(Vertex, Vertex, Option<usize>)]) -> (Vec<usize>, usize) {
+    let reachable: Vec<usize> = distances.iter().filter_map(|&(_, _, distance)| distance).collect();
+    let unreachable = distances.len() - reachable.len();
+    (reachable, unreachable)
+}
+
+// result of estimate_average_distance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AverageDistanceEstimate {
+    pub average: Option<f64>,    // mean distance over the reachable pairs, None if none of them were reachable
+    pub reachable_fraction: f64, // how many of the sampled pairs had a path at all, 0.0 to 1.0
+}
+
+// estimate of the average shortest path length from `num_pairs` random pairs of nodes, which is what the
+// distances analysis in main does with 1000 pairs. the pairs are unique and come from pair_up_nodes_seeded
+// with `seed`, so it errors the same way when the graph doesn't have enough nodes.
+// unreachable pairs don't go into the average (their distance is infinite), they only lower reachable_fraction
+pub fn estimate_average_distance(graph: &Graph, num_pairs: usize, seed: u64) -> Result<AverageDistanceEstimate, Box<dyn Error>> {
+    let pairs = pair_up_nodes_seeded(graph.nodes().collect(), num_pairs, seed)?;
+    let (reachable, _) = split_reachable(&pair_distances(graph, &pairs));
+
+    let average = (!reachable.is_empty()).then(|| reachable.iter().sum::<usize>() as f64 / reachable.len() as f64);
+    let reachable_fraction = if pairs.is_empty() { 0.0 } else { reachable.len() as f64 / pairs.len() as f64 };
+    Ok(AverageDistanceEstimate { average, reachable_fraction })
+}
+
+// eccentricity of v: the distance to the farthest node it can reach. None when v can't reach anything else
+// (an isolated node, or one that isn't in the graph)
+pub fn eccentricity(graph: &Graph, v: Vertex) -> Option<usize> {
+    bfs_distances(graph, v).into_values().max().filter(|&farthest| farthest > 0)
+}
+
+// eccentricity of every node that has one, the smallest ones are the central nodes and the biggest the peripheral ones.
+// this is a bfs per node so it's O(V*E), fine for small graphs or subgraphs but not the whole twitch graph
+pub fn all_eccentricities(graph: &Graph) -> HashMap<Vertex, usize> {
+    graph.nodes().filter_map(|v| eccentricity(graph, v).map(|e| (v, e))).collect()
+}
+
+// eccentricity of every node in the largest connected component, a component that's one node on its own gets 0
+// this runs bfs from each of those nodes so it's O(V*E), way too slow for the whole twitch graph
+// (bfs_all isn't used here, each bfs result is only needed for its max so there's no point keeping V maps around)
+fn largest_component_eccentricities(graph: &Graph) -> Vec<usize> {
+    largest_component(graph).iter().map(|&node| eccentricity(graph, node).unwrap_or(0)).collect()
+}
+
+// longest shortest path in the graph. if the graph isn't connected this is the diameter of the
+// largest connected component (the true diameter would be infinite), and None for an empty graph
+pub fn diameter(graph: &Graph) -> Option<usize> {
+    largest_component_eccentricities(graph).into_iter().max()
+}
+
+// smallest eccentricity, using the same largest component rule as diameter
+pub fn radius(graph: &Graph) -> Option<usize> {
+    largest_component_eccentricities(graph).into_iter().min()
+}
+
+// above this many nodes floyd_warshall prints a warning, the n^2 matrix and n^3 loop get slow fast
+pub const FLOYD_WARSHALL_WARN_NODES: usize = 2000;
+
+// every shortest distance at once with floyd-warshall, source used: https://en.wikipedia.org/wiki/Floyd%E2%80%93Warshall_algorithm
+// keyed by (start, end) and pairs with no path aren't in the map, like bfs_distances. it's O(V^3) time and
+// O(V^2) memory so it's only for small (dense) graphs or subgraphs, for anything bigger a bfs per start is better
+pub fn floyd_warshall(graph: &Graph) -> HashMap<(Vertex, Vertex), usize> {
+    let labels = Relabeling::new(graph.nodes().collect());
+    let n = labels.len();
+    if n > FLOYD_WARSHALL_WARN_NODES {
+        eprintln!("warning: floyd_warshall on {} nodes is O(V^3) and needs a {}x{} matrix, this will take a while", n, n, n);
+    }
+
+    let mut matrix: Vec<Option<usize>> = vec![None; n * n];
+    for (i, &v) in labels.inverse().iter().enumerate() {
+        matrix[i * n + i] = Some(0);
+        for neighbor in graph.neighbors(v) {
+            let j = labels.to_new(neighbor).unwrap();
+            if i != j {
+                matrix[i * n + j] = Some(1);
+            }
+        }
+    }
+    for k in 0..n {
+        for i in 0..n {
+            let Some(through_k) = matrix[i * n + k] else { continue };
+            for j in 0..n {
+                if let Some(k_to_j) = matrix[k * n + j] {
+                    let candidate = through_k + k_to_j;
+                    if matrix[i * n + j].is_none_or(|current| candidate < current) {
+                        matrix[i * n + j] = Some(candidate);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut distances = HashMap::new();
+    for (index, distance) in matrix.into_iter().enumerate() {
+        if let Some(distance) = distance {
+            distances.insert((labels.to_original(index / n), labels.to_original(index % n)), distance);
+        }
+    }
+    distances
+}
+
+// farthest node from start and how far it is, the smallest id wins a tie so runs are repeatable
+fn farthest_from(graph: &Graph, start: Vertex) -> (Vertex, usize) {
+    bfs_distances(graph, start)
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+        .unwrap_or((start, 0))
+}
+
+// quick estimate of the diameter with the double sweep trick: bfs from a random node to find the node farthest
+// from it, then bfs again from that one and take the farthest distance. this is done `samples` times from
+// different random starts (picked with `seed`) and the biggest distance found is returned.
+// every distance it finds is a real shortest path so this is a lower bound, it's never more than the diameter
+// and on real graphs it's usually right or very close. it's 2 * samples bfs runs instead of one per node,
+// so seconds instead of forever on the twitch graph. 0 for an empty graph
+pub fn estimated_diameter(graph: &Graph, samples: usize, seed: u64) -> usize {
+    let mut nodes: Vec<Vertex> = graph.nodes().collect();
+    nodes.sort_unstable(); //hashmap order changes between runs, sorting keeps the seed meaningful
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut best = 0;
+    for _ in 0..samples {
+        let Some(&start) = nodes.choose(&mut rng) else { break };
+        let (far, _) = farthest_from(graph, start);
+        best = best.max(farthest_from(graph, far).1);
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{bfs_distances, build_weighted_adjacency_list, Direction, Graph};
+
+    #[test]
+    fn dijkstra_takes_the_lighter_path() {
+        // 1 -> 2 directly costs 10, but going 1 -> 3 -> 4 -> 2 only costs 3
+        let edges = vec![(1, 2, 10.0), (1, 3, 1.0), (3, 4, 1.0), (4, 2, 1.0), (5, 6, 1.0)];
+        let distances = dijkstra(&build_weighted_adjacency_list(&edges), 1);
+        assert_eq!(distances[&2], 3.0);
+        assert_eq!(distances[&4], 2.0);
+        assert_eq!(distances.get(&5), None); //not reachable from 1
+
+        let unweighted: Vec<_> = edges.iter().map(|&(u, v, _)| (u, v)).collect();
+        assert_eq!(bfs_distances(&Graph::from_edges(&unweighted), 1)[&2], 1); //bfs only counts hops
+    }
+
+    #[test]
+    fn bfs_path_goes_from_start_to_end() {
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 4), (1, 5), (5, 4), (6, 7)]);
+        let path = bfs_path(&graph, 1, 4).unwrap();
+        assert_eq!(path.len(), 3); //1-5-4 is shorter than 1-2-3-4
+        assert_eq!(path, vec![1, 5, 4]);
+        assert_eq!(bfs_path(&graph, 3, 3), Some(vec![3]));
+        assert_eq!(bfs_path(&graph, 1, 7), None);
+    }
+
+    #[test]
+    fn pair_distances_match_plain_bfs() {
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 4), (5, 6)]);
+        let pairs = vec![(1, 4), (2, 1), (1, 3), (1, 6), (5, 6), (1, 4)]; //1 shows up as a start a few times
+        let distances = pair_distances(&graph, &pairs);
+        assert_eq!(distances, vec![(1, 4, Some(3)), (2, 1, Some(1)), (1, 3, Some(2)), (1, 6, None), (5, 6, Some(1)), (1, 4, Some(3))]);
+        assert_eq!(split_reachable(&distances), (vec![3, 1, 2, 1, 3], 1));
+        assert_eq!(distance(&graph, 1, 4), Some(3));
+        assert_eq!(distance(&graph, 1, 6), None);
+
+        let mut calls = Vec::new();
+        let with_progress = pair_distances_with_progress(&graph, &pairs, |done, total| calls.push((done, total)));
+        assert_eq!(with_progress, distances);
+        assert_eq!(calls, (1..=6).map(|done| (done, 6)).collect::<Vec<_>>());
+
+        let cache = bfs_all(&graph, &[1, 5, 1]);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache[&1], bfs_distances(&graph, 1));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_bfs_matches_sequential() {
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 4), (4, 1), (5, 6), (6, 7)]);
+        let sources = vec![1, 3, 5, 7, 3, 42];
+        assert_eq!(parallel_bfs_all(&graph, &sources), bfs_all(&graph, &sources));
+    }
+
+    #[test]
+    fn diameter_and_radius() {
+        // path 1-2-3-4-5 has diameter 4 and radius 2 (from 3), the 6-7 piece is smaller so it's ignored
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5), (6, 7)]);
+        assert_eq!(diameter(&graph), Some(4));
+        assert_eq!(radius(&graph), Some(2));
+        assert_eq!(diameter(&Graph::default()), None);
+        assert_eq!(radius(&Graph::default()), None);
+    }
+
+    #[test]
+    fn parallel_edges_keep_smallest_weight() {
+        let graph = build_weighted_adjacency_list(&[(1, 2, 4.0), (2, 1, 2.5)]);
+        assert_eq!(graph[&1][&2], 2.5);
+        assert_eq!(dijkstra(&graph, 2)[&1], 2.5);
+    }
+
+    #[test]
+    fn bidirectional_matches_bfs() {
+        //a cycle of 7 with a chord and a tail, plus a separate piece
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5), (5, 6), (6, 7), (7, 1), (2, 6), (4, 8), (8, 9), (10, 11)]);
+        for start in 1..=11 {
+            for end in 1..=11 {
+                assert_eq!(bidirectional_bfs(&graph, start, end), distance(&graph, start, end), "{} to {}", start, end);
+            }
+        }
+        let directed = Graph::from_edges_with_direction(&[(1, 2), (2, 3)], Direction::Directed);
+        assert_eq!(bidirectional_bfs(&directed, 1, 3), Some(2));
+        assert_eq!(bidirectional_bfs(&directed, 3, 1), None);
+    }
+
+    #[test]
+    fn connected_pairs() {
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5), (2, 6), (7, 8), (8, 9)]);
+        assert!(are_connected(&graph, 1, 5));
+        assert!(are_connected(&graph, 6, 4));
+        assert!(!are_connected(&graph, 1, 9));
+        assert!(!are_connected(&graph, 9, 42));
+        assert!(are_connected(&graph, 3, 3));
+
+        let directed = Graph::from_edges_with_direction(&[(1, 2), (2, 3)], Direction::Directed);
+        assert!(are_connected(&directed, 1, 3));
+        assert!(!are_connected(&directed, 3, 1));
+    }
+
+    #[test]
+    fn floyd_warshall_matches_bfs() {
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 4), (1, 5), (5, 4), (6, 7), (8, 8)]);
+        let all_pairs = floyd_warshall(&graph);
+        for start in graph.nodes() {
+            for (end, distance) in bfs_distances(&graph, start) {
+                assert_eq!(all_pairs[&(start, end)], distance);
+            }
+        }
+        assert_eq!(all_pairs.len(), 5 * 5 + 2 * 2 + 1);
+        assert_eq!(all_pairs.get(&(1, 6)), None);
+
+        let directed = Graph::from_edges_with_direction(&[(1, 2), (2, 3)], Direction::Directed);
+        let all_pairs = floyd_warshall(&directed);
+        assert_eq!(all_pairs[&(1, 3)], 2);
+        assert_eq!(all_pairs.get(&(3, 1)), None);
+    }
+
+    #[test]
+    fn eccentricities() {
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 4), (5, 5)]);
+        assert_eq!(eccentricity(&graph, 1), Some(3));
+        assert_eq!(eccentricity(&graph, 2), Some(2));
+        assert_eq!(eccentricity(&graph, 5), None); //only a self-loop
+        assert_eq!(eccentricity(&graph, 99), None);
+        assert_eq!(all_eccentricities(&graph), HashMap::from([(1, 3), (2, 2), (3, 2), (4, 3)]));
+    }
+
+    #[test]
+    fn double_sweep_is_a_lower_bound() {
+        //a path 1-2-...-8 with a small loop hanging off 4, the diameter is 7
+        let mut edges: Vec<Edge> = (1..8).map(|v| (v, v + 1)).collect();
+        edges.extend([(4, 20), (20, 21), (21, 4)]);
+        let graph = Graph::from_edges(&edges);
+        assert_eq!(estimated_diameter(&graph, 3, 42), 7);
+        assert_eq!(estimated_diameter(&graph, 3, 42), estimated_diameter(&graph, 3, 42));
+        assert!(estimated_diameter(&graph, 1, 7) <= diameter(&graph).unwrap());
+        assert_eq!(estimated_diameter(&Graph::default(), 5, 1), 0);
+    }
+
+    #[test]
+    fn sampled_average_distance() {
+        //a triangle and a separate edge: 3 of the 10 possible pairs are in the triangle (distance 1), 1 is the edge
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 1), (4, 5)]);
+        let estimate = estimate_average_distance(&graph, 10, 3).unwrap();
+        assert_eq!(estimate, AverageDistanceEstimate { average: Some(1.0), reachable_fraction: 0.4 });
+        assert!(estimate_average_distance(&graph, 11, 3).is_err());
+
+        let none = estimate_average_distance(&graph, 0, 3).unwrap();
+        assert_eq!(none, AverageDistanceEstimate { average: None, reachable_fraction: 0.0 });
+    }
+}
@@ -0,0 +1,88 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+use std::error::Error;
+
+use crate::graph::{Edge, Vertex};
+
+// takes the list of nodes and pairs them up randomly 
+// every pair is different (either way around) and never a node with itself, so this errors if there
+// aren't enough nodes to make that many unique pairs
+pub fn pair_up_nodes(nodes: Vec<Vertex>, num_pairs: usize) -> Result<Vec<Edge>, Box<dyn Error>> {
+    pair_up_nodes_with_rng(nodes, num_pairs, &mut rand::thread_rng())
+}
+
+// same as pair_up_nodes but the same seed always gives the same pairs, so a run can be reproduced
+// the nodes get sorted first since a list that came out of a HashSet is in a different order every run
+pub fn pair_up_nodes_seeded(mut nodes: Vec<Vertex>, num_pairs: usize, seed: u64) -> Result<Vec<Edge>, Box<dyn Error>> {
+    nodes.sort_unstable();
+    pair_up_nodes_with_rng(nodes, num_pairs, &mut StdRng::seed_from_u64(seed))
+}
+
+// how many random draws we allow per pair before giving up on finding new unique pairs
+const MAX_TRIES_PER_PAIR: usize = 100;
+
+fn pair_up_nodes_with_rng<R: Rng>(nodes: Vec<Vertex>, num_pairs: usize, rng: &mut R) -> Result<Vec<Edge>, Box<dyn Error>> {
+    let distinct_nodes = nodes.iter().collect::<HashSet<_>>().len();
+    if num_pairs > 0 && distinct_nodes < 2 {
+        return Err(format!("need at least 2 nodes to make pairs, got {}", distinct_nodes).into());
+    }
+    let possible_pairs = distinct_nodes * distinct_nodes.saturating_sub(1) / 2;
+    if num_pairs > possible_pairs {
+        return Err(format!("asked for {} unique pairs but {} nodes only make {}", num_pairs, distinct_nodes, possible_pairs).into());
+    }
+
+    let mut pairs = Vec::new(); //empty vector to store the pairs of nodes
+    let mut seen: HashSet<Edge> = HashSet::new(); //pairs picked so far as (smaller, bigger) so (1, 2) and (2, 1) count as the same
+    let mut tries = 0;
+
+    while pairs.len() < num_pairs {
+        tries += 1;
+        if tries > num_pairs * MAX_TRIES_PER_PAIR {
+            return Err(format!("only found {} unique pairs out of {} after {} tries", pairs.len(), num_pairs, tries - 1).into());
+        }
+
+        let selected_nodes: Vec<Vertex> = nodes.choose_multiple(rng, 2).cloned().collect(); //iterates over nodes and picks two random ones to form a pair, used this source: https://www.reddit.com/r/rust/comments/r4ovyl/how_to_choose_a_random_string_or_integer_from_a/ 
+        let (u, v) = (selected_nodes[0], selected_nodes[1]);
+        if u != v && seen.insert((u.min(v), u.max(v))) { //skip self-pairs (from repeated nodes in the list) and pairs we already have
+            pairs.push((u, v)); //puts pairs into empty vector
+        }
+    }
+
+    Ok(pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn too_few_nodes() {
+        assert!(pair_up_nodes(vec![], 3).is_err());
+        assert!(pair_up_nodes(vec![7], 1).is_err());
+        assert_eq!(pair_up_nodes(vec![7], 0).unwrap(), vec![]); //asking for no pairs is fine
+        assert_eq!(pair_up_nodes(vec![], 0).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn pairs_are_unique() {
+        let pairs = pair_up_nodes(vec![1, 2, 3, 4], 6).unwrap(); //every possible pair
+        let canonical: HashSet<Edge> = pairs.iter().map(|&(u, v)| (u.min(v), u.max(v))).collect();
+        assert_eq!(canonical.len(), 6);
+        assert!(pairs.iter().all(|&(u, v)| u != v));
+
+        assert!(pair_up_nodes(vec![1, 2], 2).is_err()); //only (1, 2) exists
+        assert!(pair_up_nodes(vec![1, 1, 2], 2).is_err()); //repeats don't make new pairs
+    }
+
+    #[test]
+    fn same_seed_same_pairs() {
+        let nodes: Vec<Vertex> = (0..50).collect();
+        let mut shuffled = nodes.clone();
+        shuffled.reverse();
+        let pairs = pair_up_nodes_seeded(nodes.clone(), 20, 42).unwrap();
+        assert_eq!(pairs, pair_up_nodes_seeded(shuffled, 20, 42).unwrap()); //order of the input doesn't matter
+        assert_ne!(pairs, pair_up_nodes_seeded(nodes, 20, 43).unwrap());
+    }
+}
@@ -0,0 +1,68 @@
+use crate::graph::{Graph, Vertex};
+
+// the neighbors u and v have in common. goes through the smaller of the two sets and looks each one up in the
+// other, so it's cheap even when one of them is a big hub
+fn shared_neighbors(graph: &Graph, u: Vertex, v: Vertex) -> impl Iterator<Item = Vertex> + '_ {
+    let (small, big) = if graph.degree(u) <= graph.degree(v) { (u, v) } else { (v, u) };
+    graph.neighbors(small).filter(move |&w| graph.has_edge(big, w))
+}
+
+// jaccard similarity of two nodes' neighborhoods: |N(u) ∩ N(v)| / |N(u) ∪ N(v)|, so 1.0 when they have exactly
+// the same neighbors and 0.0 when they share none. 0.0 when both have no neighbors (or aren't in the graph).
+// for a directed graph it's the out-neighbors, like who both users follow
+pub fn jaccard_similarity(graph: &Graph, u: Vertex, v: Vertex) -> f64 {
+    let shared = common_neighbors(graph, u, v);
+    let union = graph.degree(u) + graph.degree(v) - shared;
+    if union == 0 {
+        return 0.0;
+    }
+    shared as f64 / union as f64
+}
+
+// number of neighbors u and v share, the simplest link prediction score: two users who follow a lot of the
+// same people are more likely to end up following each other
+pub fn common_neighbors(graph: &Graph, u: Vertex, v: Vertex) -> usize {
+    shared_neighbors(graph, u, v).count()
+}
+
+// adamic-adar score, source used: https://www.cs.cornell.edu/home/kleinber/link-pred.pdf (liben-nowell & kleinberg)
+// like common_neighbors but each shared neighbor w adds 1 / ln(degree(w)), so sharing a small account counts for
+// more than both following a huge streamer. a shared neighbor with degree 1 would be dividing by ln(1) = 0, that
+// can only happen in a directed graph or when u == v, and it's skipped. 0.0 when they share nothing
+pub fn adamic_adar(graph: &Graph, u: Vertex, v: Vertex) -> f64 {
+    shared_neighbors(graph, u, v)
+        .map(|w| graph.degree(w))
+        .filter(|&degree| degree > 1)
+        .map(|degree| 1.0 / (degree as f64).ln())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jaccard() {
+        // 1 and 2 both link to 3 and 4, 1 also links to 5
+        let graph = Graph::from_edges(&[(1, 3), (1, 4), (1, 5), (2, 3), (2, 4)]);
+        assert!((jaccard_similarity(&graph, 1, 2) - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(jaccard_similarity(&graph, 3, 4), 1.0); //both have neighbors 1 and 2
+        assert_eq!(jaccard_similarity(&graph, 1, 3), 0.0);
+        assert_eq!(jaccard_similarity(&graph, 98, 99), 0.0);
+    }
+
+    #[test]
+    fn link_prediction_scores() {
+        // 1 and 2 share 3 and 4, and 4 is also linked to 5 and 6 so it's less special than 3
+        let graph = Graph::from_edges(&[(1, 3), (1, 4), (2, 3), (2, 4), (4, 5), (4, 6)]);
+        assert_eq!(common_neighbors(&graph, 1, 2), 2);
+        assert_eq!(common_neighbors(&graph, 2, 1), 2);
+        assert_eq!(common_neighbors(&graph, 1, 5), 1);
+        assert_eq!(common_neighbors(&graph, 1, 99), 0);
+
+        let expected = 1.0 / 2f64.ln() + 1.0 / 4f64.ln(); //3 has degree 2, 4 has degree 4
+        assert!((adamic_adar(&graph, 1, 2) - expected).abs() < 1e-9);
+        assert!(adamic_adar(&graph, 5, 6) < adamic_adar(&graph, 1, 2));
+        assert_eq!(adamic_adar(&graph, 3, 5), 0.0);
+    }
+}
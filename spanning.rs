@@ -0,0 +1,43 @@
+use crate::csr::Relabeling;
+use crate::graph::{Vertex, WeightedEdge};
+use crate::union_find::UnionFind;
+
+// minimum spanning tree with kruskal's algorithm: go through the edges from lightest to heaviest and keep
+// one whenever it joins two pieces that weren't connected yet. edges are treated as undirected.
+// when the graph isn't connected this gives a spanning forest, one tree per component.
+// edges with the same weight are taken in the order they were given so the result is always the same
+pub fn minimum_spanning_tree(weighted_edges: &[WeightedEdge]) -> Vec<WeightedEdge> {
+    let vertices: Vec<Vertex> = weighted_edges.iter().flat_map(|&(u, v, _)| [u, v]).collect();
+    let labels = Relabeling::new(vertices);
+    let mut sets = UnionFind::new(labels.len());
+
+    let mut sorted: Vec<WeightedEdge> = weighted_edges.to_vec();
+    sorted.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+    let mut tree = Vec::with_capacity(labels.len().saturating_sub(1));
+    for (u, v, weight) in sorted {
+        if sets.union(labels.to_new(u).unwrap(), labels.to_new(v).unwrap()) {
+            tree.push((u, v, weight));
+        }
+    }
+    tree
+}
+
+// total weight of a tree (or any list of edges), like from minimum_spanning_tree
+pub fn total_weight(edges: &[WeightedEdge]) -> f64 {
+    edges.iter().map(|&(_, _, weight)| weight).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kruskal_tree_and_forest() {
+        let edges = [(1, 2, 4.0), (2, 3, 1.0), (1, 3, 2.0), (3, 4, 5.0), (2, 4, 3.0), (10, 11, 7.0), (11, 11, 0.5)];
+        let tree = minimum_spanning_tree(&edges);
+        assert_eq!(tree, vec![(2, 3, 1.0), (1, 3, 2.0), (2, 4, 3.0), (10, 11, 7.0)]);
+        assert_eq!(total_weight(&tree), 13.0);
+        assert!(minimum_spanning_tree(&[]).is_empty());
+    }
+}
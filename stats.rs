@@ -0,0 +1,206 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+
+use crate::graph::{Graph, Vertex};
+use crate::paths::split_reachable;
+
+// summary of the degrees in the graph, all zeros for an empty graph
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DegreeStats {
+    pub mean: f64,
+    pub median: f64,
+    pub min: usize,
+    pub max: usize,
+    pub std_dev: f64, // population standard deviation
+}
+
+// mean, median, min, max and standard deviation of the node degrees
+// the median is the middle of the sorted degrees (average of the two middle ones for an even count)
+pub fn degree_stats(graph: &Graph) -> DegreeStats {
+    let mut degrees: Vec<usize> = graph.nodes().map(|node| graph.degree(node)).collect();
+    if degrees.is_empty() {
+        return DegreeStats { mean: 0.0, median: 0.0, min: 0, max: 0, std_dev: 0.0 };
+    }
+    degrees.sort_unstable();
+
+    let n = degrees.len();
+    let mean = degrees.iter().sum::<usize>() as f64 / n as f64;
+    let median = median(&degrees);
+    let variance = degrees.iter().map(|&d| (d as f64 - mean).powi(2)).sum::<f64>() / n as f64; //second pass now that we know the mean
+
+    DegreeStats { mean, median, min: degrees[0], max: degrees[n - 1], std_dev: variance.sqrt() }
+}
+
+// the k nodes with the highest degree as (node, degree), highest first and the smaller id first on a tie.
+// a heap that never holds more than k nodes keeps the best ones so far, so it's O(V log k) instead of sorting everything
+pub fn top_degree_nodes(graph: &Graph, k: usize) -> Vec<(Vertex, usize)> {
+    let mut heap: BinaryHeap<Reverse<(usize, Reverse<Vertex>)>> = BinaryHeap::with_capacity(k + 1); //the top of this is the worst one kept
+    for v in graph.nodes() {
+        heap.push(Reverse((graph.degree(v), Reverse(v))));
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+    heap.into_sorted_vec().into_iter().map(|Reverse((degree, Reverse(v)))| (v, degree)).collect()
+}
+
+// degree assortativity: the pearson correlation between the degrees at the two ends of every edge, from -1 to 1.
+// positive means hubs link to other hubs, negative means hubs mostly link to low degree nodes (like a star).
+// undirected edges are counted both ways around so it's symmetric. for a directed graph it's out-degree of the
+// source against out-degree of the target. 0.0 when it isn't defined (no edges, or every end has the same degree)
+pub fn degree_assortativity(graph: &Graph) -> f64 {
+    let ends: Vec<(f64, f64)> = graph
+        .nodes()
+        .flat_map(|u| graph.neighbors(u).map(move |v| (graph.degree(u) as f64, graph.degree(v) as f64)))
+        .collect();
+    if ends.is_empty() {
+        return 0.0;
+    }
+
+    let n = ends.len() as f64;
+    let mean_x = ends.iter().map(|&(x, _)| x).sum::<f64>() / n;
+    let mean_y = ends.iter().map(|&(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = ends.iter().map(|&(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let spread_x: f64 = ends.iter().map(|&(x, _)| (x - mean_x).powi(2)).sum();
+    let spread_y: f64 = ends.iter().map(|&(_, y)| (y - mean_y).powi(2)).sum();
+    if spread_x == 0.0 || spread_y == 0.0 {
+        return 0.0;
+    }
+    covariance / (spread_x.sqrt() * spread_y.sqrt())
+}
+
+// middle of an already sorted, non-empty list (average of the two middle ones for an even count)
+fn median(sorted: &[usize]) -> f64 {
+    let n = sorted.len();
+    if n % 2 == 1 {
+        sorted[n / 2] as f64
+    } else {
+        (sorted[n / 2 - 1] + sorted[n / 2]) as f64 / 2.0
+    }
+}
+
+// summary of the distances from paths::pair_distances. the mean, median and max only use the pairs that
+// had a path, the rest are just counted, so they're None when nothing was reachable
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistanceStats {
+    pub reachable: usize,
+    pub unreachable: usize,
+    pub mean: Option<f64>,
+    pub median: Option<f64>,
+    pub max: Option<usize>,
+}
+
+pub fn distance_stats(distances: &[(Vertex, Vertex, Option<usize>)]) -> DistanceStats {
+    let (mut reachable, unreachable) = split_reachable(distances);
+    reachable.sort_unstable();
+    let n = reachable.len();
+    DistanceStats {
+        reachable: n,
+        unreachable,
+        mean: (n > 0).then(|| reachable.iter().sum::<usize>() as f64 / n as f64),
+        median: (n > 0).then(|| median(&reachable)),
+        max: reachable.last().copied(),
+    }
+}
+
+// degree -> how many nodes have that degree, a BTreeMap so it comes out sorted by degree
+pub fn degree_distribution(graph: &Graph) -> BTreeMap<usize, usize> {
+    let mut distribution = BTreeMap::new();
+    for node in graph.nodes() {
+        *distribution.entry(graph.degree(node)).or_insert(0) += 1;
+    }
+    distribution
+}
+
+// smallest degree in a distribution from degree_distribution, None if there are no nodes
+pub fn min_degree(distribution: &BTreeMap<usize, usize>) -> Option<usize> {
+    distribution.keys().next().copied()
+}
+
+// largest degree in the distribution
+pub fn max_degree(distribution: &BTreeMap<usize, usize>) -> Option<usize> {
+    distribution.keys().next_back().copied()
+}
+
+// the most common degree, if there's a tie the smaller degree wins
+pub fn mode_degree(distribution: &BTreeMap<usize, usize>) -> Option<usize> {
+    distribution
+        .iter()
+        .max_by(|(degree_a, count_a), (degree_b, count_b)| count_a.cmp(count_b).then(degree_b.cmp(degree_a)))
+        .map(|(&degree, _)| degree)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distribution_of_a_star() {
+        // 1 is in the middle with degree 4, the rest have degree 1, and 6-7 is its own little edge
+        let graph = Graph::from_edges(&[(1, 2), (1, 3), (1, 4), (1, 5), (6, 7)]);
+        let distribution = degree_distribution(&graph);
+        assert_eq!(distribution, BTreeMap::from([(1, 6), (4, 1)]));
+        assert_eq!(min_degree(&distribution), Some(1));
+        assert_eq!(max_degree(&distribution), Some(4));
+        assert_eq!(mode_degree(&distribution), Some(1));
+    }
+
+    #[test]
+    fn stats_of_a_star() {
+        let graph = Graph::from_edges(&[(1, 2), (1, 3), (1, 4), (1, 5)]); //degrees are 4, 1, 1, 1, 1
+        let stats = degree_stats(&graph);
+        assert_eq!(stats.mean, 1.6);
+        assert_eq!(stats.median, 1.0);
+        assert_eq!((stats.min, stats.max), (1, 4));
+        assert!((stats.std_dev - 1.2).abs() < 1e-9);
+
+        let path = Graph::from_edges(&[(1, 2), (2, 3), (3, 4)]); //degrees are 1, 2, 2, 1 so the median is between the middle two
+        assert_eq!(degree_stats(&path).median, 1.5);
+        assert_eq!(degree_stats(&Graph::default()).mean, 0.0);
+    }
+
+    #[test]
+    fn distance_stats_skip_unreachable() {
+        let distances = [(1, 2, Some(1)), (1, 3, None), (2, 4, Some(4)), (3, 4, Some(2)), (5, 6, None)];
+        let stats = distance_stats(&distances);
+        assert_eq!(stats, DistanceStats { reachable: 3, unreachable: 2, mean: Some(7.0 / 3.0), median: Some(2.0), max: Some(4) });
+        assert_eq!(distance_stats(&[(1, 2, None)]).mean, None);
+    }
+
+    #[test]
+    fn star_is_disassortative() {
+        let star = Graph::from_edges(&[(1, 2), (1, 3), (1, 4), (1, 5)]);
+        assert!((degree_assortativity(&star) + 1.0).abs() < 1e-9);
+
+        //two stars with their hubs joined, the hub-hub edge pulls it up a little but it's still negative
+        let stars = Graph::from_edges(&[(1, 2), (1, 3), (1, 4), (5, 6), (5, 7), (5, 8), (1, 5)]);
+        let r = degree_assortativity(&stars);
+        assert!(r < 0.0 && r > -1.0);
+
+        let cycle = Graph::from_edges(&[(1, 2), (2, 3), (3, 1)]); //every degree is 2
+        assert_eq!(degree_assortativity(&cycle), 0.0);
+        assert_eq!(degree_assortativity(&Graph::default()), 0.0);
+    }
+
+    #[test]
+    fn top_hubs() {
+        let graph = Graph::from_edges(&[(1, 2), (1, 3), (1, 4), (5, 6), (5, 7), (5, 8), (2, 3), (9, 10)]);
+        assert_eq!(top_degree_nodes(&graph, 3), vec![(1, 3), (5, 3), (2, 2)]); //1 and 5 tie, then 2 beats 3 on id
+        assert_eq!(top_degree_nodes(&graph, 0), vec![]);
+        assert_eq!(top_degree_nodes(&graph, 100).len(), 10);
+    }
+
+    #[test]
+    fn empty_distribution() {
+        let distribution = degree_distribution(&Graph::default());
+        assert!(distribution.is_empty());
+        assert_eq!(min_degree(&distribution), None);
+        assert_eq!(mode_degree(&distribution), None);
+    }
+
+    #[test]
+    fn mode_tie_goes_to_smaller_degree() {
+        let distribution = BTreeMap::from([(2, 5), (3, 5), (7, 1)]);
+        assert_eq!(mode_degree(&distribution), Some(2));
+    }
+}
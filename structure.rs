@@ -0,0 +1,415 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt;
+
+use crate::graph::{induced_subgraph, Edge, Graph, Vertex};
+
+// tries to two-color the graph with bfs: every node gets the opposite color of the node it was found from.
+// a fresh coloring starts in each connected component. returns the colors (true/false are the two sides)
+// or None as soon as an edge joins two nodes of the same color. direction is ignored for directed graphs
+pub fn bipartite_coloring(graph: &Graph) -> Option<HashMap<Vertex, bool>> {
+    if graph.is_directed() {
+        return bipartite_coloring(&graph.to_undirected());
+    }
+
+    let mut colors: HashMap<Vertex, bool> = HashMap::new();
+    for start in graph.nodes() {
+        if colors.contains_key(&start) {
+            continue;
+        }
+        colors.insert(start, true);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(current) = queue.pop_front() {
+            let color = colors[&current];
+            for neighbor in graph.neighbors(current) {
+                match colors.get(&neighbor) {
+                    Some(&neighbor_color) if neighbor_color == color => return None, //conflict edge
+                    Some(_) => {}
+                    None => {
+                        colors.insert(neighbor, !color);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+    }
+
+    Some(colors)
+}
+
+// whether the graph can be split into two sides with every edge going between them
+pub fn is_bipartite(graph: &Graph) -> bool {
+    bipartite_coloring(graph).is_some()
+}
+
+// k-core: keep removing nodes with degree less than k (which lowers their neighbors' degrees) until every
+// node left has at least k neighbors left, then return what's left. meant for undirected graphs
+pub fn k_core(graph: &Graph, k: usize) -> Graph {
+    let mut degrees: HashMap<Vertex, usize> = graph.nodes().map(|v| (v, graph.degree(v))).collect();
+    let mut removed: HashSet<Vertex> = HashSet::new();
+    let mut queue: VecDeque<Vertex> = degrees.iter().filter(|&(_, &d)| d < k).map(|(&v, _)| v).collect();
+
+    while let Some(v) = queue.pop_front() {
+        if !removed.insert(v) {
+            continue;
+        }
+        for neighbor in graph.neighbors(v) {
+            let degree = degrees.get_mut(&neighbor).unwrap();
+            *degree = degree.saturating_sub(1);
+            if *degree < k && !removed.contains(&neighbor) {
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    let remaining: HashSet<Vertex> = graph.nodes().filter(|v| !removed.contains(v)).collect();
+    induced_subgraph(graph, &remaining)
+}
+
+// core number of every node: the biggest k where the node is still in the k-core
+// peels off the node with the smallest degree left each time, source used: https://arxiv.org/abs/cs/0310049
+pub fn core_numbers(graph: &Graph) -> HashMap<Vertex, usize> {
+    let mut degrees: HashMap<Vertex, usize> = graph.nodes().map(|v| (v, graph.degree(v))).collect();
+    let mut by_degree: BTreeSet<(usize, Vertex)> = degrees.iter().map(|(&v, &d)| (d, v)).collect();
+    let mut cores = HashMap::new();
+    let mut k = 0;
+
+    while let Some((degree, v)) = by_degree.pop_first() {
+        k = k.max(degree);
+        cores.insert(v, k);
+        for neighbor in graph.neighbors(v) {
+            if cores.contains_key(&neighbor) {
+                continue; //already peeled off
+            }
+            let neighbor_degree = degrees.get_mut(&neighbor).unwrap();
+            by_degree.remove(&(*neighbor_degree, neighbor));
+            *neighbor_degree = neighbor_degree.saturating_sub(1);
+            by_degree.insert((*neighbor_degree, neighbor));
+        }
+    }
+
+    cores
+}
+
+// one frame of the iterative dfs in low_link_dfs: the node, where it came from, and how far through its neighbors we are
+struct LowLinkFrame {
+    node: Vertex,
+    parent: Option<Vertex>,
+    neighbors: Vec<Vertex>,
+    next: usize,
+}
+
+// dfs that tracks discovery times and low-links (the earliest discovery time reachable from a node's subtree
+// using one back edge), source used: https://cp-algorithms.com/graph/cutpoints.html
+// it's iterative with an explicit stack like graph::dfs so a long path can't overflow the call stack.
+// on_tree_edge(parent, child, low[child], disc[parent]) gets called when the dfs is done with child.
+// returns the roots of the dfs trees and how many children each one has in its tree
+fn low_link_dfs(graph: &Graph, mut on_tree_edge: impl FnMut(Vertex, Vertex, usize, usize)) -> Vec<(Vertex, usize)> {
+    let mut discovery: HashMap<Vertex, usize> = HashMap::new();
+    let mut low: HashMap<Vertex, usize> = HashMap::new();
+    let mut roots = Vec::new();
+
+    for root in graph.nodes() {
+        if discovery.contains_key(&root) {
+            continue;
+        }
+        let mut root_children = 0;
+        discovery.insert(root, discovery.len());
+        low.insert(root, discovery[&root]);
+        let mut stack = vec![LowLinkFrame { node: root, parent: None, neighbors: graph.neighbors(root).collect(), next: 0 }];
+
+        while let Some(frame) = stack.last_mut() {
+            let v = frame.node;
+            if frame.next < frame.neighbors.len() {
+                let w = frame.neighbors[frame.next];
+                frame.next += 1;
+                if Some(w) == frame.parent {
+                    continue; //going straight back to the parent isn't a back edge
+                }
+                if let Some(&w_discovery) = discovery.get(&w) {
+                    let v_low = low.get_mut(&v).unwrap();
+                    *v_low = (*v_low).min(w_discovery);
+                } else {
+                    discovery.insert(w, discovery.len());
+                    low.insert(w, discovery[&w]);
+                    stack.push(LowLinkFrame { node: w, parent: Some(v), neighbors: graph.neighbors(w).collect(), next: 0 });
+                }
+            } else {
+                let parent = frame.parent;
+                stack.pop();
+                if let Some(p) = parent {
+                    let v_low = low[&v];
+                    let p_low = low.get_mut(&p).unwrap();
+                    *p_low = (*p_low).min(v_low);
+                    if p == root {
+                        root_children += 1;
+                    }
+                    on_tree_edge(p, v, v_low, discovery[&p]);
+                }
+            }
+        }
+        roots.push((root, root_children));
+    }
+
+    roots
+}
+
+// articulation points (cut vertices): nodes whose removal splits their component into more pieces.
+// a non-root node p is one when some dfs child can't get above p without going through it (low[child] >= disc[p]),
+// and a dfs root is one when it has more than one child. meant for undirected graphs
+pub fn articulation_points(graph: &Graph) -> HashSet<Vertex> {
+    let mut points = HashSet::new();
+    let mut root_set = HashSet::new();
+    let mut candidates = Vec::new();
+
+    let roots = low_link_dfs(graph, |parent, _, child_low, parent_discovery| {
+        if child_low >= parent_discovery {
+            candidates.push(parent);
+        }
+    });
+    for (root, children) in roots {
+        root_set.insert(root);
+        if children > 1 {
+            points.insert(root);
+        }
+    }
+    points.extend(candidates.into_iter().filter(|v| !root_set.contains(v))); //roots follow the children rule instead
+
+    points
+}
+
+// bridges (cut edges): edges whose removal splits their component in two.
+// a dfs tree edge p-child is one when nothing in child's subtree reaches p or above (low[child] > disc[p]).
+// each bridge is listed once as (smaller, bigger), sorted. meant for undirected graphs
+pub fn bridges(graph: &Graph) -> Vec<Edge> {
+    let mut bridges = Vec::new();
+    low_link_dfs(graph, |parent, child, child_low, parent_discovery| {
+        if child_low > parent_discovery {
+            bridges.push((parent.min(child), parent.max(child)));
+        }
+    });
+    bridges.sort_unstable();
+    bridges
+}
+
+// whether the graph has a cycle.
+// directed: dfs that marks nodes as "in progress" (gray) while they're on the dfs stack, reaching an
+// in-progress node again means we went around a cycle. undirected: any edge to an already visited node
+// that isn't the one we just came from closes a cycle, and a self-loop counts as one. the adjacency list
+// already merged repeated edges so use has_cycle_in_edges if a doubled edge should count
+pub fn has_cycle(graph: &Graph) -> bool {
+    if graph.is_directed() {
+        has_directed_cycle(graph)
+    } else {
+        has_undirected_cycle(graph)
+    }
+}
+
+fn has_directed_cycle(graph: &Graph) -> bool {
+    let mut in_progress: HashSet<Vertex> = HashSet::new();
+    let mut done: HashSet<Vertex> = HashSet::new();
+
+    for start in graph.nodes() {
+        if done.contains(&start) {
+            continue;
+        }
+        in_progress.insert(start);
+        let mut stack: Vec<(Vertex, Vec<Vertex>)> = vec![(start, graph.neighbors(start).collect())];
+
+        while let Some((node, neighbors)) = stack.last_mut() {
+            match neighbors.pop() {
+                Some(next) if in_progress.contains(&next) => return true, //back edge
+                Some(next) if !done.contains(&next) => {
+                    in_progress.insert(next);
+                    stack.push((next, graph.neighbors(next).collect()));
+                }
+                Some(_) => {}
+                None => {
+                    in_progress.remove(node);
+                    done.insert(*node);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    false
+}
+
+fn has_undirected_cycle(graph: &Graph) -> bool {
+    let mut visited: HashSet<Vertex> = HashSet::new();
+
+    for start in graph.nodes() {
+        if !visited.insert(start) {
+            continue;
+        }
+        let mut stack: Vec<(Vertex, Option<Vertex>)> = vec![(start, None)]; //node and the node we came from
+
+        while let Some((node, parent)) = stack.pop() {
+            for next in graph.neighbors(node) {
+                if Some(next) == parent {
+                    continue;
+                }
+                if !visited.insert(next) {
+                    return true; //self-loop or another way to a node we already have
+                }
+                stack.push((next, Some(node)));
+            }
+        }
+    }
+
+    false
+}
+
+// cycle check on an undirected edge list before it goes into a HashSet, so a self-loop or the same edge
+// showing up twice (either way around) counts as a cycle too
+pub fn has_cycle_in_edges(edges: &[Edge]) -> bool {
+    let mut seen: HashSet<Edge> = HashSet::new();
+    for &(u, v) in edges {
+        if u == v || !seen.insert((u.min(v), u.max(v))) {
+            return true;
+        }
+    }
+    has_undirected_cycle(&Graph::from_edges(edges))
+}
+
+// error from topological_sort when the graph isn't acyclic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    pub remaining: Vec<Vertex>, // nodes that never got to in-degree 0: the ones on a cycle and everything after them, sorted
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "graph has a cycle, {} nodes couldn't be ordered: {:?}", self.remaining.len(), self.remaining)
+    }
+}
+
+impl Error for CycleError {}
+
+// topological order of a directed graph with kahn's algorithm: keep taking a node nothing points to anymore
+// (in-degree 0) and removing its out-edges. when there's a tie the smaller node goes first so the order is
+// always the same. if nodes are left over that never got to in-degree 0 there's a cycle and that's an error.
+// an undirected graph counts every edge as going both ways, so anything with an edge is a cycle there
+pub fn topological_sort(graph: &Graph) -> Result<Vec<Vertex>, CycleError> {
+    let mut in_degrees: HashMap<Vertex, usize> = graph.nodes().map(|v| (v, 0)).collect();
+    for v in graph.nodes() {
+        for neighbor in graph.neighbors(v) {
+            *in_degrees.get_mut(&neighbor).unwrap() += 1;
+        }
+    }
+
+    let mut ready: BTreeSet<Vertex> = in_degrees.iter().filter(|&(_, &d)| d == 0).map(|(&v, _)| v).collect();
+    let mut order = Vec::with_capacity(graph.num_nodes());
+    while let Some(v) = ready.pop_first() {
+        order.push(v);
+        for neighbor in graph.neighbors(v) {
+            let in_degree = in_degrees.get_mut(&neighbor).unwrap();
+            *in_degree -= 1;
+            if *in_degree == 0 {
+                ready.insert(neighbor);
+            }
+        }
+    }
+
+    if order.len() < graph.num_nodes() {
+        let mut remaining: Vec<Vertex> = in_degrees.into_iter().filter(|&(_, d)| d > 0).map(|(v, _)| v).collect();
+        remaining.sort_unstable();
+        return Err(CycleError { remaining });
+    }
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Direction;
+
+    #[test]
+    fn even_cycle_is_bipartite() {
+        let square = Graph::from_edges(&[(1, 2), (2, 3), (3, 4), (4, 1), (5, 6)]);
+        let colors = bipartite_coloring(&square).unwrap();
+        assert_eq!(colors[&1], colors[&3]);
+        assert_ne!(colors[&1], colors[&2]);
+        assert_ne!(colors[&5], colors[&6]);
+        assert!(is_bipartite(&square));
+    }
+
+    #[test]
+    fn odd_cycle_is_not_bipartite() {
+        let triangle = Graph::from_edges(&[(1, 2), (2, 3), (3, 1)]);
+        assert!(!is_bipartite(&triangle));
+        assert_eq!(bipartite_coloring(&triangle), None);
+    }
+
+    #[test]
+    fn cores() {
+        // K4 on 1-4 with a path 4-5-6 hanging off it
+        let graph = Graph::from_edges(&[(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4), (4, 5), (5, 6)]);
+        assert_eq!(k_core(&graph, 3), Graph::from_edges(&[(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)]));
+        assert_eq!(k_core(&graph, 2).num_nodes(), 4); //5 goes once 6 is removed
+        assert_eq!(k_core(&graph, 4).num_nodes(), 0);
+
+        let cores = core_numbers(&graph);
+        assert_eq!(cores[&1], 3);
+        assert_eq!(cores[&4], 3);
+        assert_eq!(cores[&5], 1);
+        assert_eq!(cores[&6], 1);
+    }
+
+    #[test]
+    fn barbell_articulation_point() {
+        // two triangles 1-2-3 and 3-4-5 joined at node 3, so 3 is the only cut vertex
+        let barbell = Graph::from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 3)]);
+        assert_eq!(articulation_points(&barbell), HashSet::from([3]));
+
+        // with a middle node 4 between the triangles instead, 3, 4 and 5 all disconnect something
+        let longer = Graph::from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 6), (6, 7), (7, 5)]);
+        assert_eq!(articulation_points(&longer), HashSet::from([3, 4, 5]));
+        assert!(articulation_points(&Graph::from_edges(&[(1, 2), (2, 3), (3, 1)])).is_empty());
+    }
+
+    #[test]
+    fn bridge_between_triangles() {
+        let graph = Graph::from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 6), (6, 4)]);
+        assert_eq!(bridges(&graph), vec![(3, 4)]);
+
+        let path = Graph::from_edges(&[(3, 2), (2, 1)]);
+        assert_eq!(bridges(&path), vec![(1, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn cycles() {
+        let dag = Graph::from_edges_with_direction(&[(1, 2), (1, 3), (2, 4), (3, 4)], Direction::Directed);
+        assert!(!has_cycle(&dag));
+        let directed_cycle = Graph::from_edges_with_direction(&[(1, 2), (2, 3), (3, 1), (3, 4)], Direction::Directed);
+        assert!(has_cycle(&directed_cycle));
+
+        let tree = Graph::from_edges(&[(1, 2), (1, 3), (3, 4), (3, 5)]);
+        assert!(!has_cycle(&tree));
+        assert!(has_cycle(&Graph::from_edges(&[(1, 2), (2, 3), (3, 1)])));
+        assert!(has_cycle(&Graph::from_edges(&[(1, 2), (2, 2)]))); //self-loop
+
+        assert!(!has_cycle_in_edges(&[(1, 2), (1, 3), (3, 4)]));
+        assert!(has_cycle_in_edges(&[(1, 2), (2, 1)])); //the same edge twice
+        assert!(has_cycle_in_edges(&[(1, 2), (2, 3), (3, 1)]));
+    }
+
+    #[test]
+    fn topological_order() {
+        let edges = vec![(5, 1), (1, 2), (1, 3), (2, 4), (3, 4)];
+        let dag = Graph::from_edges_with_direction(&edges, Direction::Directed);
+        let order = topological_sort(&dag).unwrap();
+        assert_eq!(order, vec![5, 1, 2, 3, 4]);
+        for (u, v) in edges {
+            let position = |x| order.iter().position(|&y| y == x).unwrap();
+            assert!(position(u) < position(v));
+        }
+
+        let cyclic = Graph::from_edges_with_direction(&[(1, 2), (2, 3), (3, 2), (3, 4)], Direction::Directed);
+        let err = topological_sort(&cyclic).unwrap_err();
+        assert_eq!(err.remaining, vec![2, 3, 4]);
+        assert!(err.to_string().contains("cycle"));
+    }
+}
@@ -0,0 +1,86 @@
+// union-find (disjoint-set) over the indices 0..n, for kruskal, counting components and anything else that
+// keeps merging groups. vertices with big or sparse ids can go through csr::Relabeling first
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    sets: usize,
+}
+
+impl UnionFind {
+    // n sets with one element each
+    pub fn new(n: usize) -> UnionFind {
+        UnionFind { parent: (0..n).collect(), rank: vec![0; n], sets: n }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    // how many separate sets there are right now
+    pub fn num_sets(&self) -> usize {
+        self.sets
+    }
+
+    // representative of x's set, everything on the way gets pointed straight at it (path compression).
+    // done in two passes instead of recursion so long chains are fine
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut node = x;
+        while self.parent[node] != root {
+            let next = self.parent[node];
+            self.parent[node] = root;
+            node = next;
+        }
+        root
+    }
+
+    // merges the sets of a and b, hanging the shorter tree under the taller one (union by rank).
+    // false when they were already in the same set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.rank[a] < self.rank[b] {
+            self.parent[a] = b;
+        } else {
+            self.parent[b] = a;
+            if self.rank[a] == self.rank[b] {
+                self.rank[a] += 1;
+            }
+        }
+        self.sets -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_and_find() {
+        let mut sets = UnionFind::new(6);
+        assert_eq!(sets.num_sets(), 6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(1, 2));
+        assert!(!sets.union(0, 2));
+        assert!(sets.union(4, 5));
+        assert!(sets.connected(2, 0));
+        assert!(!sets.connected(3, 4));
+        assert_eq!(sets.num_sets(), 3);
+        assert_eq!(sets.len(), 6);
+    }
+}